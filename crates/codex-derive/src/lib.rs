@@ -0,0 +1,106 @@
+// codex-derive: proc-macro crate providing `#[derive(Timestamped)]`, which generates
+// `fn timestamp(&self) -> i64` dispatch across every variant of an event enum, keyed
+// off a field named `timestamp` (or marked `#[timestamp]` when the field is named
+// something else). A variant missing that field is a compile error rather than a
+// hand-written match arm someone forgot to update.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields};
+
+#[proc_macro_derive(Timestamped, attributes(timestamp))]
+pub fn derive_timestamped(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let data_enum = match &input.data {
+        Data::Enum(data_enum) => data_enum,
+        _ => {
+            return syn::Error::new_spanned(&input, "Timestamped can only be derived for enums")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let mut arms = Vec::new();
+    let mut timestamp_ty = None;
+
+    for variant in &data_enum.variants {
+        let variant_ident = &variant.ident;
+
+        let field = match &variant.fields {
+            Fields::Named(named) => named.named.iter().find(|field| field_marks_timestamp(field)),
+            _ => None,
+        };
+
+        let field = match field {
+            Some(field) => field,
+            None => {
+                return syn::Error::new_spanned(
+                    variant,
+                    format!(
+                        "variant `{}` has no field named `timestamp` (or marked `#[timestamp]`) \
+                         required by #[derive(Timestamped)]",
+                        variant_ident
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        };
+
+        let field_name = field.ident.clone();
+        timestamp_ty.get_or_insert_with(|| field.ty.clone());
+
+        arms.push(quote! {
+            #name::#variant_ident { #field_name, .. } => #field_name.clone(),
+        });
+    }
+
+    // Every variant is required to carry the same field name, so they share a type too;
+    // fall back to `i64` only in the (unreachable) case of a unit/empty enum.
+    let timestamp_ty = timestamp_ty.unwrap_or_else(|| syn::parse_quote!(i64));
+
+    let expanded = quote! {
+        impl #name {
+            pub fn timestamp(&self) -> #timestamp_ty {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+fn field_marks_timestamp(field: &Field) -> bool {
+    if field.ident.as_ref().is_some_and(|ident| ident == "timestamp") {
+        return true;
+    }
+    field.attrs.iter().any(|attr| attr.path().is_ident("timestamp"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(src: &str) -> Field {
+        syn::parse_str(src).unwrap()
+    }
+
+    #[test]
+    fn field_named_timestamp_is_recognized_without_an_attribute() {
+        assert!(field_marks_timestamp(&field("timestamp: i64")));
+    }
+
+    #[test]
+    fn field_marked_with_attribute_is_recognized_under_any_name() {
+        assert!(field_marks_timestamp(&field("#[timestamp] created_at: i64")));
+    }
+
+    #[test]
+    fn unrelated_field_is_not_recognized() {
+        assert!(!field_marks_timestamp(&field("message: String")));
+    }
+}
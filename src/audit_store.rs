@@ -0,0 +1,151 @@
+// Async audit-export subsystem - streams TimelineEvents into a queryable SQLite table
+// so past sessions (messages, tool calls, security findings, MCP connects) can be
+// reviewed after the fact without the UI ever blocking on disk I/O.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+
+use crate::types::{to_unix_seconds, TimelineEvent};
+
+const BATCH_SIZE: usize = 50;
+const BATCH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+struct AuditRecord {
+    session_id: String,
+    timestamp: i64,
+    event: TimelineEvent,
+}
+
+/// Streams `TimelineEvent`s into a backing SQLite table keyed by session id and
+/// timestamp, batching writes behind an mpsc channel so `record()` never blocks on I/O.
+pub struct AuditStore {
+    sender: mpsc::UnboundedSender<AuditRecord>,
+    db_path: PathBuf,
+}
+
+impl AuditStore {
+    /// Opens (or creates) the backing database and spawns the background writer task.
+    /// Must be called from within a Tokio runtime.
+    pub fn new(db_path: &str) -> Result<Self> {
+        let path = PathBuf::from(db_path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create audit store directory")?;
+        }
+
+        // Run schema setup on the calling thread so an early `record()` call can't
+        // race the background writer's own `CREATE TABLE`.
+        let conn = Connection::open(&path).context("Failed to open audit store")?;
+        Self::initialize_schema(&conn)?;
+        drop(conn);
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(Self::writer_loop(path.clone(), receiver));
+
+        eprintln!("Audit store initialized at: {:?}", path);
+
+        Ok(Self { sender, db_path: path })
+    }
+
+    fn initialize_schema(conn: &Connection) -> Result<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS audit_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                event_json TEXT NOT NULL
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_audit_events_session ON audit_events(session_id, timestamp)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Enqueue an event for a session. Returns as soon as the record is queued - the
+    /// actual write happens on the background writer task, batched with whatever else
+    /// has queued up since the last flush.
+    pub fn record(&self, session_id: impl Into<String>, event: TimelineEvent) -> Result<()> {
+        let record = AuditRecord {
+            session_id: session_id.into(),
+            timestamp: to_unix_seconds(event.timestamp()),
+            event,
+        };
+        self.sender
+            .send(record)
+            .map_err(|_| anyhow::anyhow!("audit store writer has shut down"))
+    }
+
+    /// Reload a past session's timeline, oldest first.
+    pub async fn load_session(&self, session_id: String) -> Result<Vec<TimelineEvent>> {
+        let path = self.db_path.clone();
+        tokio::task::spawn_blocking(move || -> Result<Vec<TimelineEvent>> {
+            let conn = Connection::open(&path).context("Failed to open audit store")?;
+            let mut stmt = conn.prepare(
+                "SELECT event_json FROM audit_events WHERE session_id = ?1 ORDER BY timestamp ASC",
+            )?;
+            let rows = stmt.query_map(params![session_id], |row| row.get::<_, String>(0))?;
+
+            let mut events = Vec::new();
+            for row in rows {
+                let json = row?;
+                events.push(
+                    serde_json::from_str(&json).context("Failed to deserialize timeline event")?,
+                );
+            }
+            Ok(events)
+        })
+        .await
+        .context("Audit store query task panicked")?
+    }
+
+    /// Drains the channel in batches, flushing either once `BATCH_SIZE` records have
+    /// queued up or `BATCH_INTERVAL` has elapsed with at least one record pending.
+    async fn writer_loop(db_path: PathBuf, mut receiver: mpsc::UnboundedReceiver<AuditRecord>) {
+        let conn = match Connection::open(&db_path) {
+            Ok(conn) => conn,
+            Err(err) => {
+                eprintln!("Audit store writer failed to open database: {}", err);
+                return;
+            }
+        };
+
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        loop {
+            batch.clear();
+
+            match tokio::time::timeout(BATCH_INTERVAL, receiver.recv()).await {
+                Ok(Some(record)) => batch.push(record),
+                Ok(None) => return, // all senders dropped
+                Err(_) => continue, // interval elapsed with nothing queued
+            }
+
+            while batch.len() < BATCH_SIZE {
+                match receiver.try_recv() {
+                    Ok(record) => batch.push(record),
+                    Err(_) => break,
+                }
+            }
+
+            if let Err(err) = Self::write_batch(&conn, &batch) {
+                eprintln!("Audit store batch write failed: {}", err);
+            }
+        }
+    }
+
+    fn write_batch(conn: &Connection, batch: &[AuditRecord]) -> Result<()> {
+        for record in batch {
+            let json = serde_json::to_string(&record.event)?;
+            conn.execute(
+                "INSERT INTO audit_events (session_id, timestamp, event_json) VALUES (?1, ?2, ?3)",
+                params![record.session_id, record.timestamp, json],
+            )?;
+        }
+        Ok(())
+    }
+}
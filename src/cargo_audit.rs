@@ -0,0 +1,173 @@
+// cargo-audit / RustSec advisory ingestion - shells out to `cargo audit --json` and
+// maps each reported vulnerability onto a `TimelineEvent::SecurityFinding`, so a
+// dependency audit shows up in the same finding cards as any other scanner's output.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::process::Command;
+
+use crate::types::{now_timestamp, FindingIdentifier, TimelineEvent};
+use crate::ui::security_finding::IdentifierKind;
+
+pub struct CargoAudit;
+
+impl CargoAudit {
+    /// Runs `cargo audit --json` against the crate rooted at `manifest_dir` and maps
+    /// every reported vulnerability into a `SecurityFinding` timeline event.
+    ///
+    /// Returns an empty vec (rather than an error) when no vulnerabilities are found,
+    /// matching `cargo audit`'s own exit-code convention of a clean run still emitting
+    /// a report with an empty `vulnerabilities.list`.
+    pub fn scan(manifest_dir: &str) -> Result<Vec<TimelineEvent>> {
+        let output = Command::new("cargo")
+            .arg("audit")
+            .arg("--json")
+            .current_dir(manifest_dir)
+            .output()
+            .context("Failed to run `cargo audit` - is cargo-audit installed?")?;
+
+        // cargo-audit exits non-zero when it finds vulnerabilities, so don't gate on
+        // status - its JSON report is the signal either way.
+        let report: AuditReport = serde_json::from_slice(&output.stdout)
+            .context("Failed to parse `cargo audit --json` output")?;
+
+        Ok(report.vulnerabilities.list.into_iter().map(Self::to_finding).collect())
+    }
+
+    fn to_finding(vuln: Vulnerability) -> TimelineEvent {
+        let advisory = vuln.advisory;
+        let package = vuln.package;
+
+        let mut identifiers: Vec<FindingIdentifier> = advisory
+            .aliases
+            .into_iter()
+            .map(|alias| {
+                let kind = IdentifierKind::infer(&alias).label().to_lowercase();
+                FindingIdentifier { kind, value: alias, url: None }
+            })
+            .collect();
+        identifiers.push(FindingIdentifier {
+            kind: "rustsec".to_string(),
+            value: advisory.id.clone(),
+            url: Some(format!("https://rustsec.org/advisories/{}.html", advisory.id)),
+        });
+
+        let recommendation = if vuln.versions.patched.is_empty() {
+            "No patched version is available yet - track the advisory for updates.".to_string()
+        } else {
+            format!("Upgrade {} to one of: {}", package.name, vuln.versions.patched.join(", "))
+        };
+
+        // cargo-audit's JSON report doesn't carry a severity itself (that's a CVSS
+        // score it leaves to the caller to bucket); treat every reported advisory
+        // as high until a scoring pass exists to downgrade informational ones.
+        TimelineEvent::SecurityFinding {
+            vulnerability_id: advisory.id,
+            severity: "high".to_string(),
+            confidence: Some("confirmed".to_string()),
+            title: advisory.title,
+            description: advisory.description,
+            file_path: format!("{} {}", package.name, package.version),
+            line_number: None,
+            identifiers,
+            recommendation,
+            affected_instances: Vec::new(),
+            references: Vec::new(),
+            timestamp: now_timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AuditReport {
+    vulnerabilities: Vulnerabilities,
+}
+
+#[derive(Debug, Deserialize)]
+struct Vulnerabilities {
+    list: Vec<Vulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Vulnerability {
+    advisory: Advisory,
+    versions: VersionRange,
+    package: Package,
+}
+
+#[derive(Debug, Deserialize)]
+struct Advisory {
+    id: String,
+    title: String,
+    description: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionRange {
+    #[serde(default)]
+    patched: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    name: String,
+    version: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vuln_with_aliases(aliases: Vec<&str>) -> Vulnerability {
+        Vulnerability {
+            advisory: Advisory {
+                id: "RUSTSEC-2024-0001".to_string(),
+                title: "title".to_string(),
+                description: "description".to_string(),
+                aliases: aliases.into_iter().map(str::to_string).collect(),
+            },
+            versions: VersionRange { patched: vec!["1.2.3".to_string()] },
+            package: Package { name: "some-crate".to_string(), version: "1.0.0".to_string() },
+        }
+    }
+
+    fn identifiers(event: TimelineEvent) -> Vec<FindingIdentifier> {
+        match event {
+            TimelineEvent::SecurityFinding { identifiers, .. } => identifiers,
+            other => panic!("expected a SecurityFinding event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ghsa_aliases_are_labeled_as_ghsa_with_an_advisories_url() {
+        let event = CargoAudit::to_finding(vuln_with_aliases(vec!["GHSA-xxxx-yyyy-zzzz"]));
+        let found = identifiers(event);
+
+        let alias = found.iter().find(|id| id.value == "GHSA-xxxx-yyyy-zzzz").unwrap();
+        assert_eq!(alias.kind, "ghsa");
+        assert_eq!(
+            IdentifierKind::parse(&alias.kind).canonical_url(&alias.value),
+            Some("https://github.com/advisories/GHSA-xxxx-yyyy-zzzz".to_string())
+        );
+    }
+
+    #[test]
+    fn cve_aliases_are_labeled_as_cve() {
+        let event = CargoAudit::to_finding(vuln_with_aliases(vec!["CVE-2024-12345"]));
+        let found = identifiers(event);
+
+        let alias = found.iter().find(|id| id.value == "CVE-2024-12345").unwrap();
+        assert_eq!(alias.kind, "cve");
+    }
+
+    #[test]
+    fn the_advisory_id_is_always_included_as_a_rustsec_identifier() {
+        let event = CargoAudit::to_finding(vuln_with_aliases(vec![]));
+        let found = identifiers(event);
+
+        let rustsec = found.iter().find(|id| id.value == "RUSTSEC-2024-0001").unwrap();
+        assert_eq!(rustsec.kind, "rustsec");
+    }
+}
@@ -0,0 +1,198 @@
+// Client-side implementation of the fs/terminal capabilities advertised to
+// codex-acp during `initialize` - the agent issues `fs/read_text_file`,
+// `fs/write_text_file`, and `terminal/*` as JSON-RPC *requests* to us, and
+// these handlers are what actually services them.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+/// The current state of a terminal the agent created, as returned by
+/// `terminal_output`.
+#[derive(Debug, Clone, Default)]
+pub struct TerminalSnapshot {
+    pub output: String,
+    pub exit_code: Option<i32>,
+}
+
+/// What `initialize`'s advertised `fs`/`terminal` capabilities actually do
+/// when the agent calls them. Behind a trait so an embedder can swap in a
+/// different sandboxing policy (or a read-only one, or one backed by a remote
+/// filesystem) without touching the JSON-RPC plumbing in `codex_adapter`.
+pub trait ClientCapabilities: Send + Sync {
+    fn read_text_file(&self, path: &str) -> Result<String>;
+    fn write_text_file(&self, path: &str, content: &str) -> Result<()>;
+    fn create_terminal(&self, command: &str, args: Vec<String>, cwd: Option<String>) -> Result<String>;
+    fn terminal_output(&self, terminal_id: &str) -> Result<TerminalSnapshot>;
+    fn wait_for_exit(&self, terminal_id: &str) -> Result<i32>;
+    fn kill_terminal(&self, terminal_id: &str) -> Result<()>;
+    fn release_terminal(&self, terminal_id: &str) -> Result<()>;
+}
+
+struct TerminalHandle {
+    child: Child,
+    output: Arc<Mutex<String>>,
+}
+
+/// The default `ClientCapabilities`: file reads/writes are resolved relative
+/// to (and refused outside of) the session's `repo_path`, and terminals are
+/// plain child processes whose combined stdout/stderr is buffered in memory
+/// for `terminal_output` to poll.
+pub struct SandboxedClientCapabilities {
+    repo_path: Arc<Mutex<Option<String>>>,
+    terminals: Mutex<HashMap<String, TerminalHandle>>,
+    next_terminal_id: Mutex<u64>,
+}
+
+impl SandboxedClientCapabilities {
+    /// `repo_path` is the same handle `CodexAdapter` updates in
+    /// `create_session`, so the sandbox root tracks whichever repository the
+    /// active session is for.
+    pub fn new(repo_path: Arc<Mutex<Option<String>>>) -> Self {
+        Self {
+            repo_path,
+            terminals: Mutex::new(HashMap::new()),
+            next_terminal_id: Mutex::new(1),
+        }
+    }
+
+    /// Resolves `path` against the session's repo root and refuses it if it
+    /// would land outside that root (via `..`, a symlink, or an absolute path
+    /// elsewhere), so the agent can't read or write files outside the
+    /// repository it was given.
+    fn resolve_sandboxed(&self, path: &str) -> Result<PathBuf> {
+        let repo_path = self.repo_path.lock().unwrap().clone()
+            .ok_or_else(|| anyhow!("No active session repo path"))?;
+        let root = std::fs::canonicalize(&repo_path)
+            .with_context(|| format!("Failed to canonicalize repo path {}", repo_path))?;
+
+        let candidate = Path::new(path);
+        let joined = if candidate.is_absolute() {
+            candidate.to_path_buf()
+        } else {
+            root.join(candidate)
+        };
+
+        // A file about to be created can't be canonicalized directly - fall
+        // back to canonicalizing its parent and rejoining the file name.
+        let resolved = match std::fs::canonicalize(&joined) {
+            Ok(resolved) => resolved,
+            Err(_) => {
+                let parent = joined.parent()
+                    .ok_or_else(|| anyhow!("Invalid path: {}", path))?;
+                let file_name = joined.file_name()
+                    .ok_or_else(|| anyhow!("Invalid path: {}", path))?;
+                std::fs::canonicalize(parent)
+                    .with_context(|| format!("Failed to resolve parent directory of {}", path))?
+                    .join(file_name)
+            }
+        };
+
+        if !resolved.starts_with(&root) {
+            bail!("Path {} escapes the session's repository root {}", path, root.display());
+        }
+
+        Ok(resolved)
+    }
+
+    fn next_terminal_id(&self) -> String {
+        let mut next_id = self.next_terminal_id.lock().unwrap();
+        let id = format!("term-{}", *next_id);
+        *next_id += 1;
+        id
+    }
+}
+
+impl ClientCapabilities for SandboxedClientCapabilities {
+    fn read_text_file(&self, path: &str) -> Result<String> {
+        let resolved = self.resolve_sandboxed(path)?;
+        std::fs::read_to_string(&resolved)
+            .with_context(|| format!("Failed to read {:?}", resolved))
+    }
+
+    fn write_text_file(&self, path: &str, content: &str) -> Result<()> {
+        let resolved = self.resolve_sandboxed(path)?;
+        if let Some(parent) = resolved.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+        std::fs::write(&resolved, content)
+            .with_context(|| format!("Failed to write {:?}", resolved))
+    }
+
+    fn create_terminal(&self, command: &str, args: Vec<String>, cwd: Option<String>) -> Result<String> {
+        let working_dir = cwd
+            .or_else(|| self.repo_path.lock().unwrap().clone())
+            .unwrap_or_else(|| ".".to_string());
+
+        let mut child = Command::new(command)
+            .args(&args)
+            .current_dir(&working_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to spawn terminal command: {}", command))?;
+
+        let output = Arc::new(Mutex::new(String::new()));
+
+        for stream in [child.stdout.take().map(Ok), child.stderr.take().map(Err)].into_iter().flatten() {
+            let output = output.clone();
+            std::thread::spawn(move || {
+                let lines: Box<dyn Iterator<Item = std::io::Result<String>>> = match stream {
+                    Ok(stdout) => Box::new(std::io::BufReader::new(stdout).lines()),
+                    Err(stderr) => Box::new(std::io::BufReader::new(stderr).lines()),
+                };
+                for line in lines.flatten() {
+                    let mut output = output.lock().unwrap();
+                    output.push_str(&line);
+                    output.push('\n');
+                }
+            });
+        }
+
+        let terminal_id = self.next_terminal_id();
+        self.terminals.lock().unwrap().insert(terminal_id.clone(), TerminalHandle { child, output });
+
+        Ok(terminal_id)
+    }
+
+    fn terminal_output(&self, terminal_id: &str) -> Result<TerminalSnapshot> {
+        let mut terminals = self.terminals.lock().unwrap();
+        let handle = terminals.get_mut(terminal_id)
+            .ok_or_else(|| anyhow!("Unknown terminal id: {}", terminal_id))?;
+
+        let output = handle.output.lock().unwrap().clone();
+        let exit_code = handle.child.try_wait().ok().flatten().and_then(|status| status.code());
+
+        Ok(TerminalSnapshot { output, exit_code })
+    }
+
+    fn wait_for_exit(&self, terminal_id: &str) -> Result<i32> {
+        let mut terminals = self.terminals.lock().unwrap();
+        let handle = terminals.get_mut(terminal_id)
+            .ok_or_else(|| anyhow!("Unknown terminal id: {}", terminal_id))?;
+
+        let status = handle.child.wait()
+            .with_context(|| format!("Failed to wait on terminal {}", terminal_id))?;
+
+        Ok(status.code().unwrap_or(-1))
+    }
+
+    fn kill_terminal(&self, terminal_id: &str) -> Result<()> {
+        let mut terminals = self.terminals.lock().unwrap();
+        let handle = terminals.get_mut(terminal_id)
+            .ok_or_else(|| anyhow!("Unknown terminal id: {}", terminal_id))?;
+
+        handle.child.kill()
+            .with_context(|| format!("Failed to kill terminal {}", terminal_id))
+    }
+
+    fn release_terminal(&self, terminal_id: &str) -> Result<()> {
+        self.terminals.lock().unwrap().remove(terminal_id);
+        Ok(())
+    }
+}
@@ -3,21 +3,152 @@
 use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::client_capabilities::{ClientCapabilities, SandboxedClientCapabilities};
+use crate::transport::{ChildProcessTransport, NetworkTransport, Transport};
+use crate::types::{
+    FileWriteEvent, McpServerBootstrap, McpServerConfig, McpServerTransport, McpServerType,
+    ReconnectEvent, StreamEvent, TerminalOutputEvent, ToolCallEvent, ToolCallLocation,
+    ToolCallStatus, ToolCallUpdateEvent,
+};
+
+/// Directory on the remote host `codex-acp` binaries are cached under, keyed
+/// by a fingerprint of the local binary so repeated sessions to the same host
+/// skip the upload once a matching version is already there.
+const REMOTE_CACHE_DIR: &str = "~/.cache/codex-d";
+
+/// An SSH target `CodexAdapter::new_remote` launches `codex-acp` on, instead
+/// of spawning it as a local child process. Shells out to the system `ssh`
+/// binary (same approach as the rest of this codebase uses for `git`/`python`)
+/// rather than linking an SSH client library.
+#[derive(Debug, Clone)]
+pub struct SshTarget {
+    pub host: String,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_file: Option<String>,
+}
+
+impl SshTarget {
+    /// `ssh` CLI arguments identifying this target (port, identity file,
+    /// `user@host`), shared by the cache check, the upload, and the final
+    /// `codex-acp` launch so all three connect the exact same way.
+    fn connection_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if let Some(port) = self.port {
+            args.push("-p".to_string());
+            args.push(port.to_string());
+        }
+        if let Some(identity_file) = &self.identity_file {
+            args.push("-i".to_string());
+            args.push(identity_file.clone());
+        }
 
-use crate::types::{StreamEvent, ToolCallEvent, ToolCallLocation, ToolCallStatus, ToolCallUpdateEvent};
+        args.push(match &self.user {
+            Some(user) => format!("{}@{}", user, self.host),
+            None => self.host.clone(),
+        });
 
-pub struct CodexAdapter {
-    process: Arc<Mutex<Option<Child>>>,
-    stdin: Arc<Mutex<Option<ChildStdin>>>,
-    stdout: Arc<Mutex<Option<BufReader<ChildStdout>>>>,
+        args
+    }
+}
+
+/// What the reader thread hands back to a caller waiting on a specific
+/// request id or draining the notification stream: either the parsed
+/// response/notification, or `Err` once the reader has hit EOF or a parse
+/// error and is closing every channel it knows about.
+type ReaderResult = Result<JsonRpcResponse, String>;
+
+/// Requests awaiting their matching response, keyed by request id, plus the
+/// one active session's notification stream - both populated by
+/// `send_request`/`send_message` and drained exclusively by the reader thread
+/// spawned in `spawn()`.
+#[derive(Default)]
+struct ReaderRouting {
+    pending: HashMap<u64, mpsc::Sender<ReaderResult>>,
+    notifications: Option<mpsc::Sender<ReaderResult>>,
+}
+
+/// Retry/backoff limits for the reconnect supervisor started alongside the
+/// reader thread. A crash of `codex-acp` (or the connection otherwise
+/// dropping) is retried up to `max_retries` times, waiting
+/// `initial_backoff * 2^attempt` (capped at `max_backoff`) between attempts.
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    pub max_retries: u32,
+    pub initial_backoff: std::time::Duration,
+    pub max_backoff: std::time::Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: std::time::Duration::from_millis(500),
+            max_backoff: std::time::Duration::from_secs(30),
+        }
+    }
+}
+
+/// The shared, cheaply-cloneable state behind a `CodexAdapter` - every field
+/// is an `Arc` (or otherwise trivially cloneable), so cloning a handle and
+/// handing it to the reader thread shares the same transport/routing/session
+/// state rather than duplicating it. Kept separate from `CodexAdapter` itself
+/// so that only the outer, non-`Clone` type runs `Drop`; cloned handles held
+/// by background threads can come and go without tearing down the
+/// subprocess/transport out from under the owner.
+#[derive(Clone)]
+struct CodexAdapterHandle {
+    /// How the adapter reaches `codex-acp`: populated by `spawn` (which
+    /// constructs a `ChildProcessTransport`, locally or over SSH) or, for an
+    /// already-running service, by `connect` at construction time - in which
+    /// case `spawn` is a no-op. Cleared and repopulated by the supervisor on
+    /// reconnect.
+    transport: Arc<Mutex<Option<Arc<dyn Transport>>>>,
+    routing: Arc<Mutex<ReaderRouting>>,
+    reader_thread: Arc<Mutex<Option<std::thread::JoinHandle<()>>>>,
     next_id: Arc<Mutex<u64>>,
     session_id: Arc<Mutex<Option<String>>>,
-    mcp_server_process: Arc<Mutex<Option<Child>>>,
+    /// Local processes bootstrapped for registered MCP servers (one per
+    /// server config whose `bootstrap` is set), killed on drop.
+    mcp_server_processes: Arc<Mutex<Vec<Child>>>,
+    /// Names of bootstrap MCP servers already started, so a reconnect that
+    /// calls `create_session` again doesn't spawn a second copy of a server
+    /// that's still running from the first session.
+    running_mcp_servers: Arc<Mutex<HashSet<String>>>,
+    /// MCP servers to include in every session opened from here on,
+    /// populated with the built-in defaults at construction and extended via
+    /// `CodexAdapter::add_mcp_server`.
+    mcp_servers: Arc<Mutex<Vec<McpServerConfig>>>,
     repo_path: Arc<Mutex<Option<String>>>,
     system_prompt: Arc<Mutex<String>>,
+    /// Set by `new_remote`; when present, `spawn` launches `codex-acp` on this
+    /// host over SSH instead of as a local child process. Fixed at
+    /// construction time, before the adapter is ever shared, so it needs no
+    /// interior mutability.
+    remote: Option<SshTarget>,
+    /// Services the `fs/read_text_file`, `fs/write_text_file`, and
+    /// `terminal/*` requests the agent sends us, fulfilling the capabilities
+    /// advertised in `initialize`. Swappable via `with_capabilities` so an
+    /// embedder can enforce a different sandboxing policy.
+    capabilities: Arc<dyn ClientCapabilities>,
+    /// Retry/backoff limits the supervisor uses when the connection drops
+    /// unexpectedly. Overridable via `with_supervisor_config`.
+    supervisor_config: SupervisorConfig,
+    /// Set by `CodexAdapter::drop` before tearing down the transport, so the
+    /// reader thread that then sees EOF/closed knows the disconnect was
+    /// intentional and skips the reconnect supervisor.
+    shutting_down: Arc<AtomicBool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -28,7 +159,7 @@ struct JsonRpcRequest {
     params: serde_json::Value,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct JsonRpcResponse {
     jsonrpc: String,
     id: Option<u64>,
@@ -38,52 +169,641 @@ struct JsonRpcResponse {
     params: Option<serde_json::Value>,
 }
 
+/// Drives one `codex-acp` connection: spawning or connecting to it, speaking
+/// ACP JSON-RPC over whatever `Transport` it ends up with, and supervising
+/// reconnection if the connection drops. Not `Clone` - the underlying shared
+/// state lives in `CodexAdapterHandle`, which background threads hold their
+/// own clone of, so this type's `Drop` is the single point where the
+/// subprocess/transport actually gets torn down.
+pub struct CodexAdapter(CodexAdapterHandle);
+
 impl CodexAdapter {
     /// Create a new CodexAdapter instance
     pub fn new() -> Result<Self> {
-        Ok(Self {
-            process: Arc::new(Mutex::new(None)),
-            stdin: Arc::new(Mutex::new(None)),
-            stdout: Arc::new(Mutex::new(None)),
+        Ok(Self(CodexAdapterHandle::new()))
+    }
+
+    /// Create a `CodexAdapter` whose `spawn` launches `codex-acp` over SSH on
+    /// `target` instead of locally. The `repo_path`/`cwd` given to
+    /// `create_session` afterwards should then be a path on `target`, not on
+    /// this machine.
+    pub fn new_remote(target: SshTarget) -> Result<Self> {
+        let mut handle = CodexAdapterHandle::new();
+        handle.remote = Some(target);
+        Ok(Self(handle))
+    }
+
+    /// Create a `CodexAdapter` already connected to a `codex-acp` reachable at
+    /// `url` (`ws://`, `wss://`, or `tcp://`) instead of one this process
+    /// spawns - useful for sharing one long-lived agent across clients, or
+    /// reaching one running inside a container. `spawn` becomes a no-op
+    /// afterwards since the transport is already in place.
+    pub fn connect(url: &str) -> Result<Self> {
+        let handle = CodexAdapterHandle::new();
+        let transport: Arc<dyn Transport> = Arc::new(NetworkTransport::connect(url)?);
+        handle.start_reader(transport);
+        eprintln!("Connected to codex-acp at {}", url);
+        Ok(Self(handle))
+    }
+
+    /// Overrides the default sandboxed fs/terminal handling, e.g. to enforce
+    /// a different sandboxing policy or back the capabilities with a remote
+    /// filesystem.
+    pub fn with_capabilities(mut self, capabilities: Arc<dyn ClientCapabilities>) -> Self {
+        self.0.capabilities = capabilities;
+        self
+    }
+
+    /// Overrides the default reconnect-supervisor retry/backoff limits.
+    pub fn with_supervisor_config(mut self, config: SupervisorConfig) -> Self {
+        self.0.supervisor_config = config;
+        self
+    }
+
+    /// Registers an MCP server for every session opened from now on,
+    /// replacing any existing registration under the same name. Built-in
+    /// defaults (codex-psychology, deepwiki) are already registered; call
+    /// this to add more or to override one of them.
+    pub fn add_mcp_server(&self, config: McpServerConfig) {
+        self.0.add_mcp_server(config)
+    }
+
+    /// Spawn the codex-acp subprocess, locally or (if constructed via
+    /// `new_remote`) on the configured SSH target. A no-op if a transport is
+    /// already in place (e.g. this adapter was built via `connect`).
+    pub fn spawn(&self) -> Result<()> {
+        self.0.spawn()
+    }
+
+    /// Initialize ACP connection
+    pub fn initialize(&self) -> Result<()> {
+        self.0.initialize()
+    }
+
+    /// Create a new session with system prompt and repository path
+    pub fn create_session(&self, system_prompt: String, repo_path: String) -> Result<String> {
+        self.0.create_session(system_prompt, repo_path)
+    }
+
+    /// Send a message and stream responses via callback
+    pub fn send_message<F>(&self, message: String, callback: F) -> Result<()>
+    where
+        F: FnMut(StreamEvent),
+    {
+        self.0.send_message(message, callback)
+    }
+}
+
+impl Drop for CodexAdapter {
+    fn drop(&mut self) {
+        self.0.shutting_down.store(true, Ordering::SeqCst);
+
+        if let Some(transport) = self.0.transport.lock().unwrap().take() {
+            let _ = transport.shutdown();
+            eprintln!("codex-acp transport shut down");
+        }
+
+        // Kill every bootstrapped MCP server subprocess
+        for mut mcp_child in self.0.mcp_server_processes.lock().unwrap().drain(..) {
+            let _ = mcp_child.kill();
+            let _ = mcp_child.wait();
+        }
+        self.0.running_mcp_servers.lock().unwrap().clear();
+        eprintln!("MCP servers stopped");
+    }
+}
+
+impl CodexAdapterHandle {
+    fn new() -> Self {
+        let repo_path = Arc::new(Mutex::new(None));
+        let capabilities: Arc<dyn ClientCapabilities> =
+            Arc::new(SandboxedClientCapabilities::new(repo_path.clone()));
+
+        Self {
+            transport: Arc::new(Mutex::new(None)),
+            routing: Arc::new(Mutex::new(ReaderRouting::default())),
+            reader_thread: Arc::new(Mutex::new(None)),
             next_id: Arc::new(Mutex::new(1)),
             session_id: Arc::new(Mutex::new(None)),
-            mcp_server_process: Arc::new(Mutex::new(None)),
-            repo_path: Arc::new(Mutex::new(None)),
+            mcp_server_processes: Arc::new(Mutex::new(Vec::new())),
+            running_mcp_servers: Arc::new(Mutex::new(HashSet::new())),
+            mcp_servers: Arc::new(Mutex::new(Self::default_mcp_servers())),
+            repo_path,
             system_prompt: Arc::new(Mutex::new(String::new())),
-        })
+            remote: None,
+            capabilities,
+            supervisor_config: SupervisorConfig::default(),
+            shutting_down: Arc::new(AtomicBool::new(false)),
+        }
     }
 
-    /// Spawn the codex-acp subprocess
-    pub fn spawn(&self) -> Result<()> {
-        // Find codex-acp binary
-        let codex_acp_path = Self::find_codex_acp()?;
+    /// The servers every `CodexAdapter` starts out registered with, matching
+    /// what `create_session` used to hardcode: the local psychology-analysis
+    /// server (bootstrapped from its venv) and the hosted deepwiki server.
+    /// Callers can add more via `CodexAdapter::add_mcp_server`.
+    fn default_mcp_servers() -> Vec<McpServerConfig> {
+        let project_root = std::env!("CARGO_MANIFEST_DIR");
+
+        vec![
+            McpServerConfig {
+                name: "codex-psychology".to_string(),
+                transport: McpServerTransport::Http {
+                    url: "http://127.0.0.1:52848/mcp".to_string(),
+                    headers: vec![(
+                        "Accept".to_string(),
+                        "application/json, text/event-stream".to_string(),
+                    )],
+                },
+                bootstrap: Some(McpServerBootstrap {
+                    working_dir: format!("{}/mcp-servers/mcp_codex_psychology", project_root),
+                    command: "run_sse_server.py".to_string(),
+                    args: Vec::new(),
+                    venv_setup: true,
+                }),
+                readiness_timeout: Duration::from_secs(15),
+            },
+            McpServerConfig {
+                name: "deepwiki".to_string(),
+                transport: McpServerTransport::Http {
+                    url: "https://mcp.aci.dev/gateway/mcp?bundle_key=3Nhg7HK34j8ylWkv4uTeCssOKX3vdMxHfOuD".to_string(),
+                    headers: Vec::new(),
+                },
+                bootstrap: None,
+                readiness_timeout: Duration::from_secs(15),
+            },
+        ]
+    }
 
-        eprintln!("Spawning codex-acp at: {}", codex_acp_path);
+    /// Registers `config` for every session opened from now on, replacing
+    /// any existing registration with the same name.
+    fn add_mcp_server(&self, config: McpServerConfig) {
+        let mut servers = self.mcp_servers.lock().unwrap();
+        servers.retain(|existing| existing.name != config.name);
+        servers.push(config);
+    }
 
-        // Spawn subprocess
-        let mut child = Command::new(&codex_acp_path)
+    fn spawn(&self) -> Result<()> {
+        if self.transport.lock().unwrap().is_some() {
+            eprintln!("codex-acp transport already connected, skipping spawn");
+            return Ok(());
+        }
+
+        let mut command = match &self.remote {
+            Some(target) => Self::spawn_remote_command(target)?,
+            None => {
+                let codex_acp_path = Self::find_codex_acp()?;
+                eprintln!("Spawning codex-acp at: {}", codex_acp_path);
+
+                let mut command = Command::new(&codex_acp_path);
+                command
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::inherit());
+                command
+            }
+        };
+
+        let transport: Arc<dyn Transport> = Arc::new(
+            ChildProcessTransport::spawn(&mut command).context("Failed to spawn codex-acp process")?,
+        );
+        self.start_reader(transport);
+
+        eprintln!("codex-acp spawned successfully");
+
+        Ok(())
+    }
+
+    /// Stores `transport` and launches the reader thread that demultiplexes
+    /// lines read through it, shared by both `spawn` and `connect`. The
+    /// thread owns a cloned handle (all fields are `Arc`s sharing the same
+    /// underlying state) so that on an unexpected disconnect it can call
+    /// straight back into `reconnect` without threading a dozen individual
+    /// `Arc` fields through, and without affecting the owning `CodexAdapter`'s
+    /// `Drop`.
+    fn start_reader(&self, transport: Arc<dyn Transport>) {
+        *self.transport.lock().unwrap() = Some(transport.clone());
+
+        let handle = self.clone();
+        let reader_handle = std::thread::spawn(move || Self::run_reader(handle, transport));
+        *self.reader_thread.lock().unwrap() = Some(reader_handle);
+    }
+
+    /// Ensures a compatible `codex-acp` binary is cached on `target`
+    /// (uploading the local one, gzipped, if it isn't), then returns an `ssh`
+    /// `Command` configured to run it there with piped stdin/stdout - the
+    /// caller spawns it through `ChildProcessTransport`, same as the local
+    /// case, the bytes just travel over the SSH channel first.
+    fn spawn_remote_command(target: &SshTarget) -> Result<Command> {
+        let local_path = Self::find_codex_acp()?;
+        let fingerprint = Self::binary_fingerprint(&local_path)?;
+        let remote_path = format!("{}/codex-acp-{}", REMOTE_CACHE_DIR, fingerprint);
+
+        eprintln!("Checking for cached codex-acp on {}: {}", target.host, remote_path);
+
+        if !Self::remote_binary_cached(target, &remote_path)? {
+            eprintln!("No cached codex-acp found on {}, uploading {}", target.host, local_path);
+            Self::upload_binary(target, &local_path, &remote_path)?;
+            eprintln!("Uploaded codex-acp to {}:{}", target.host, remote_path);
+        } else {
+            eprintln!("Using cached codex-acp at {}:{}", target.host, remote_path);
+        }
+
+        eprintln!("Spawning codex-acp on {} via ssh", target.host);
+
+        let mut ssh_args = target.connection_args();
+        ssh_args.push(remote_path);
+
+        let mut command = Command::new("ssh");
+        command
+            .args(&ssh_args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::inherit())
+            .stderr(Stdio::inherit());
+        Ok(command)
+    }
+
+    /// A short, stable identifier for the local `codex-acp` binary's
+    /// contents, used as the remote cache key so a rebuilt binary uploads
+    /// again instead of silently reusing a stale cached one.
+    fn binary_fingerprint(path: &str) -> Result<String> {
+        let mut file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open {} to fingerprint it", path))?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)
+            .with_context(|| format!("Failed to read {} to fingerprint it", path))?;
+
+        Ok(format!("{:x}", hasher.finalize())[..16].to_string())
+    }
+
+    /// Checks whether `remote_path` already exists and is executable on
+    /// `target`, without transferring anything.
+    fn remote_binary_cached(target: &SshTarget, remote_path: &str) -> Result<bool> {
+        let mut ssh_args = target.connection_args();
+        ssh_args.push(format!("test -x {}", remote_path));
+
+        let status = Command::new("ssh")
+            .args(&ssh_args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .context("Failed to check remote codex-acp cache")?;
+
+        Ok(status.success())
+    }
+
+    /// Gzips the local binary and streams it directly into an `ssh` command
+    /// that decompresses it into the cache dir on `target` and marks it
+    /// executable - no intermediate temp file on either side.
+    fn upload_binary(target: &SshTarget, local_path: &str, remote_path: &str) -> Result<()> {
+        let mut gzip = Command::new("gzip")
+            .arg("-c")
+            .arg(local_path)
+            .stdout(Stdio::piped())
             .spawn()
-            .context("Failed to spawn codex-acp process")?;
+            .context("Failed to start local gzip to compress codex-acp for upload")?;
+
+        let gzip_stdout = gzip.stdout.take()
+            .ok_or_else(|| anyhow!("Failed to capture gzip stdout"))?;
+
+        let mut ssh_args = target.connection_args();
+        ssh_args.push(format!(
+            "mkdir -p {cache_dir} && gunzip -c > {remote_path} && chmod +x {remote_path}",
+            cache_dir = REMOTE_CACHE_DIR,
+            remote_path = remote_path,
+        ));
+
+        let upload_status = Command::new("ssh")
+            .args(&ssh_args)
+            .stdin(Stdio::from(gzip_stdout))
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .context("Failed to upload codex-acp over ssh")?;
 
-        let stdin = child.stdin.take()
-            .ok_or_else(|| anyhow!("Failed to get stdin"))?;
-        let stdout = child.stdout.take()
-            .ok_or_else(|| anyhow!("Failed to get stdout"))?;
+        let gzip_status = gzip.wait().context("Failed to wait on local gzip")?;
 
-        *self.process.lock().unwrap() = Some(child);
-        *self.stdin.lock().unwrap() = Some(stdin);
-        *self.stdout.lock().unwrap() = Some(BufReader::new(stdout));
+        if !gzip_status.success() {
+            return Err(anyhow!("Failed to gzip codex-acp for upload"));
+        }
+        if !upload_status.success() {
+            return Err(anyhow!("Remote upload of codex-acp failed"));
+        }
 
-        eprintln!("codex-acp spawned successfully");
+        Ok(())
+    }
+
+    /// Owns the transport for the lifetime of the connection so only one
+    /// thread ever reads from it, demultiplexing each line to whichever
+    /// caller is waiting for it: a response whose `id` matches a pending
+    /// request completes that request's channel, in the order lines arrive; a
+    /// notification (`method` present, no `id`) is pushed onto the active
+    /// session's notification channel. On EOF or a line that doesn't parse,
+    /// every still-pending channel is closed with an error, and - unless the
+    /// adapter is being dropped - the reconnect supervisor takes over.
+    fn run_reader(handle: CodexAdapterHandle, transport: Arc<dyn Transport>) {
+        loop {
+            let line = match transport.read_line() {
+                Ok(line) => line,
+                Err(e) => {
+                    Self::disconnected(&handle, format!("Failed to read from codex-acp transport: {}", e));
+                    return;
+                }
+            };
+
+            eprintln!("<- {}", line.trim());
+
+            let response: JsonRpcResponse = match serde_json::from_str(&line) {
+                Ok(response) => response,
+                Err(e) => {
+                    Self::disconnected(&handle, format!("Failed to parse JSON-RPC response: {}", e));
+                    return;
+                }
+            };
+
+            if let (Some(id), Some(method)) = (response.id, response.method.clone()) {
+                // Both present: this is a *request* from the agent (it wants
+                // to use an `fs`/`terminal` capability), not a reply to one of
+                // ours - service it and write our own result/error back.
+                Self::handle_agent_request(&transport, &handle.routing, &handle.capabilities, id, &method, response.params.clone());
+            } else if let Some(id) = response.id {
+                let sender = handle.routing.lock().unwrap().pending.remove(&id);
+                if let Some(sender) = sender {
+                    let _ = sender.send(Ok(response));
+                } else {
+                    eprintln!("No pending request for response id {}", id);
+                }
+            } else if response.method.is_some() {
+                let routing = handle.routing.lock().unwrap();
+                if let Some(sender) = &routing.notifications {
+                    let _ = sender.send(Ok(response));
+                }
+            }
+        }
+    }
+
+    /// Reports `message`, closes out every request waiting on a specific
+    /// response (which can never arrive now that the connection is gone),
+    /// and - unless this disconnect was caused by `Drop` - hands off to the
+    /// reconnect supervisor. The notification channel is deliberately left
+    /// open here: `reconnect` reports `client/reconnecting`/`reconnected`/
+    /// `connection_failed` through it, so an in-flight `send_message` sees
+    /// those instead of the connection just vanishing out from under it.
+    fn disconnected(handle: &CodexAdapterHandle, message: String) {
+        Self::close_pending_requests(&handle.routing, message.clone());
+
+        if handle.shutting_down.load(Ordering::SeqCst) {
+            Self::close_notifications(&handle.routing, message);
+            return;
+        }
+
+        eprintln!("codex-acp connection lost ({}), starting reconnect supervisor", message);
+        handle.reconnect();
+    }
+
+    /// Clears the dead transport and retries `spawn` + `initialize` + (if a
+    /// session had been created) `create_session`, with exponential backoff
+    /// between attempts up to `supervisor_config.max_retries`. Reports
+    /// `ConnectionReconnecting`/`ConnectionReconnected`/`ConnectionFailed` on
+    /// the active session's notification channel (if any `send_message` call
+    /// is draining it) so callers see the interruption instead of a raw
+    /// error - the channel is only closed once this sequence finally settles
+    /// (success or exhausted retries), never before.
+    fn reconnect(&self) {
+        let max_attempts = self.supervisor_config.max_retries;
+        let mut backoff = self.supervisor_config.initial_backoff;
+
+        for attempt in 1..=max_attempts {
+            self.notify_connection_event("client/reconnecting", json!({
+                "attempt": attempt,
+                "maxAttempts": max_attempts,
+            }));
+
+            eprintln!("Reconnect attempt {}/{}, waiting {:?}", attempt, max_attempts, backoff);
+            std::thread::sleep(backoff);
+            backoff = std::cmp::min(backoff * 2, self.supervisor_config.max_backoff);
+
+            match self.reestablish() {
+                Ok(()) => {
+                    eprintln!("Reconnected to codex-acp on attempt {}/{}", attempt, max_attempts);
+                    self.notify_connection_event("client/reconnected", serde_json::Value::Null);
+                    Self::close_notifications(
+                        &self.routing,
+                        "codex-acp reconnected - retry the interrupted request".to_string(),
+                    );
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Reconnect attempt {}/{} failed: {}", attempt, max_attempts, e);
+                }
+            }
+        }
+
+        let reason = format!("Exhausted {} reconnect attempts", max_attempts);
+        eprintln!("{}", reason);
+        self.notify_connection_event("client/connection_failed", json!({ "reason": reason.clone() }));
+        Self::close_notifications(&self.routing, reason);
+    }
+
+    /// Respawns the subprocess (or reconnects the network transport), redoes
+    /// `initialize`/`authenticate`, and - if a session had previously been
+    /// created - recreates it from the stored `system_prompt`/`repo_path`.
+    fn reestablish(&self) -> Result<()> {
+        *self.transport.lock().unwrap() = None;
+        self.spawn()?;
+        self.initialize()?;
+
+        let had_session = self.session_id.lock().unwrap().is_some();
+        if had_session {
+            let repo_path = self.repo_path.lock().unwrap().clone()
+                .ok_or_else(|| anyhow!("No repo path recorded for session resume"))?;
+            let system_prompt = self.system_prompt.lock().unwrap().clone();
+            self.create_session(system_prompt, repo_path)?;
+        }
 
         Ok(())
     }
 
+    /// Pushes a synthetic `ConnectionReconnecting`/`ConnectionReconnected`/
+    /// `ConnectionFailed` notification onto the active `send_message` call's
+    /// channel, reusing the same mechanism `notify_client_event` uses for
+    /// file-write/terminal-output events.
+    fn notify_connection_event(&self, method: &str, params: serde_json::Value) {
+        Self::notify_client_event(&self.routing, method, params);
+    }
+
+    /// Fulfils one `fs/*`/`terminal/*` request from the agent and writes the
+    /// JSON-RPC result (or error) back through the transport with the
+    /// original `id`.
+    fn handle_agent_request(
+        transport: &Arc<dyn Transport>,
+        routing: &Arc<Mutex<ReaderRouting>>,
+        capabilities: &Arc<dyn ClientCapabilities>,
+        id: u64,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) {
+        let outcome = Self::dispatch_agent_request(capabilities, routing, method, params);
+
+        let reply = match outcome {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32000, "message": e.to_string() },
+            }),
+        };
+
+        let json = match serde_json::to_string(&reply) {
+            Ok(json) => json,
+            Err(e) => {
+                eprintln!("Failed to serialize client capability reply: {}", e);
+                return;
+            }
+        };
+
+        match transport.send_line(&json) {
+            Ok(()) => eprintln!("-> {}", json),
+            Err(e) => eprintln!("Failed to write client capability reply: {}", e),
+        }
+    }
+
+    /// Executes one `fs/*`/`terminal/*` method against `capabilities`,
+    /// returning the JSON value to put in the response's `result` field.
+    /// File writes and terminal output also get mirrored onto the
+    /// notification channel (if a `send_message` call is actively draining
+    /// one) so the UI can surface what the agent just did.
+    fn dispatch_agent_request(
+        capabilities: &Arc<dyn ClientCapabilities>,
+        routing: &Arc<Mutex<ReaderRouting>>,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        match method {
+            "fs/read_text_file" => {
+                let path = Self::string_param(&params, "path")?;
+                let content = capabilities.read_text_file(&path)?;
+                Ok(json!({ "content": content }))
+            }
+            "fs/write_text_file" => {
+                let path = Self::string_param(&params, "path")?;
+                let content = Self::string_param(&params, "content")?;
+                capabilities.write_text_file(&path, &content)?;
+                Self::notify_client_event(routing, "client/file_write", json!({
+                    "path": path,
+                    "bytesWritten": content.len(),
+                }));
+                Ok(serde_json::Value::Null)
+            }
+            "terminal/create" => {
+                let command = Self::string_param(&params, "command")?;
+                let args = params.as_ref()
+                    .and_then(|p| p.get("args"))
+                    .and_then(|a| a.as_array())
+                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(str::to_string)).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                let cwd = params.as_ref()
+                    .and_then(|p| p.get("cwd"))
+                    .and_then(|c| c.as_str())
+                    .map(str::to_string);
+
+                let terminal_id = capabilities.create_terminal(&command, args, cwd)?;
+                Ok(json!({ "terminalId": terminal_id }))
+            }
+            "terminal/output" => {
+                let terminal_id = Self::string_param(&params, "terminalId")?;
+                let snapshot = capabilities.terminal_output(&terminal_id)?;
+                Self::notify_client_event(routing, "client/terminal_output", json!({
+                    "terminalId": terminal_id,
+                    "output": snapshot.output,
+                }));
+                Ok(json!({ "output": snapshot.output, "exitStatus": snapshot.exit_code }))
+            }
+            "terminal/wait_for_exit" => {
+                let terminal_id = Self::string_param(&params, "terminalId")?;
+                let exit_code = capabilities.wait_for_exit(&terminal_id)?;
+                Ok(json!({ "exitCode": exit_code }))
+            }
+            "terminal/kill" => {
+                let terminal_id = Self::string_param(&params, "terminalId")?;
+                capabilities.kill_terminal(&terminal_id)?;
+                Ok(serde_json::Value::Null)
+            }
+            "terminal/release" => {
+                let terminal_id = Self::string_param(&params, "terminalId")?;
+                capabilities.release_terminal(&terminal_id)?;
+                Ok(serde_json::Value::Null)
+            }
+            _ => Err(anyhow!("Unsupported client capability method: {}", method)),
+        }
+    }
+
+    fn string_param(params: &Option<serde_json::Value>, key: &str) -> Result<String> {
+        params.as_ref()
+            .and_then(|p| p.get(key))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Missing '{}' parameter", key))
+    }
+
+    /// Pushes a synthetic notification onto the active `send_message` call's
+    /// notification channel, reusing the same wire-shaped routing the reader
+    /// already uses for `session/update` so the callback translation stays in
+    /// one place. A no-op if no `send_message` call is currently draining it.
+    fn notify_client_event(routing: &Arc<Mutex<ReaderRouting>>, method: &str, params: serde_json::Value) {
+        let routing = routing.lock().unwrap();
+        if let Some(sender) = &routing.notifications {
+            let synthetic = JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: None,
+                result: None,
+                error: None,
+                method: Some(method.to_string()),
+                params: Some(params),
+            };
+            let _ = sender.send(Ok(synthetic));
+        }
+    }
+
+    /// Drains and closes every pending request channel by dropping their
+    /// senders after reporting `message`, so a caller blocked on a specific
+    /// response id unblocks with an error instead of hanging forever once the
+    /// reader can no longer make progress. Deliberately leaves the
+    /// notification channel alone - see `close_notifications` - including the
+    /// `send_message` case where a pending entry and `notifications` are the
+    /// *same* sender (it registers one `tx.clone()` under both its request id
+    /// and as the notification stream): closing that shared sender here would
+    /// still deliver this raw disconnect error onto the notification stream
+    /// ahead of anything `reconnect` reports on it, same as closing
+    /// `notifications` directly would.
+    fn close_pending_requests(routing: &Arc<Mutex<ReaderRouting>>, message: String) {
+        eprintln!("codex-acp reader thread stopping: {}", message);
+
+        let mut routing = routing.lock().unwrap();
+        let notifications = routing.notifications.clone();
+        for (_, sender) in routing.pending.drain() {
+            if notifications.as_ref().is_some_and(|notifications| sender.same_channel(notifications)) {
+                continue;
+            }
+            let _ = sender.send(Err(message.clone()));
+        }
+    }
+
+    /// Takes and closes the notification channel, reporting `message` as the
+    /// final item on it. Only called once the disconnect→reconnect sequence
+    /// has truly settled (the adapter is shutting down, reconnect succeeded,
+    /// or reconnect exhausted its retries) - never eagerly on disconnect -
+    /// so an in-flight `send_message` keeps observing reconnect progress
+    /// instead of unblocking on the first raw error.
+    fn close_notifications(routing: &Arc<Mutex<ReaderRouting>>, message: String) {
+        let mut routing = routing.lock().unwrap();
+        if let Some(sender) = routing.notifications.take() {
+            let _ = sender.send(Err(message));
+        }
+    }
+
     /// Initialize ACP connection
-    pub fn initialize(&self) -> Result<()> {
+    fn initialize(&self) -> Result<()> {
         let id = self.next_id();
 
         let request = JsonRpcRequest {
@@ -102,8 +822,8 @@ impl CodexAdapter {
             }),
         };
 
-        self.send_request(&request)?;
-        let init_response = self.read_response()?;
+        let rx = self.send_request(&request)?;
+        let init_response = Self::await_response(&rx)?;
 
         eprintln!("ACP initialized");
         eprintln!("Init response: {:?}", init_response);
@@ -128,8 +848,8 @@ impl CodexAdapter {
             }),
         };
 
-        self.send_request(&request)?;
-        let auth_response = self.read_response()?;
+        let rx = self.send_request(&request)?;
+        let auth_response = Self::await_response(&rx)?;
 
         eprintln!("Authentication response: {:?}", auth_response);
 
@@ -137,53 +857,32 @@ impl CodexAdapter {
     }
 
     /// Create a new session with system prompt and repository path
-    pub fn create_session(&self, system_prompt: String, repo_path: String) -> Result<String> {
+    fn create_session(&self, system_prompt: String, repo_path: String) -> Result<String> {
         // Store repo path for MCP server
         *self.repo_path.lock().unwrap() = Some(repo_path.clone());
 
-        // Start MCP server first
-        self.start_mcp_server()?;
+        // Bootstrap and wait for readiness of every registered MCP server
+        self.start_mcp_servers()?;
 
         let id = self.next_id();
 
         // Use the dropped repository path as working directory (not the app's directory)
         let cwd = repo_path.clone();
 
-        // Configure MCP server connection with HTTP transport (codex-acp only supports HTTP, not SSE)
-        let mcp_servers = json!([
-            {
-                "name": "codex-psychology",
-                "type": "http",
-                "url": "http://127.0.0.1:52848/mcp",
-                "headers": [
-                    {
-                        "name": "Accept",
-                        "value": "application/json, text/event-stream"
-                    }
-                ]
-            },
-            {
-                "name": "deepwiki",
-                "type": "http",
-                "url": "https://mcp.aci.dev/gateway/mcp?bundle_key=3Nhg7HK34j8ylWkv4uTeCssOKX3vdMxHfOuD",
-                "headers": []
-            }
-        ]);
-
         let request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             id,
             method: "session/new".to_string(),
             params: json!({
                 "cwd": cwd,
-                "mcpServers": mcp_servers,
+                "mcpServers": self.mcp_servers_json(),
                 "mode": "bypassPermissions",  // Skip permission prompts
                 "systemPrompt": system_prompt,
             }),
         };
 
-        self.send_request(&request)?;
-        let response = self.read_response()?;
+        let rx = self.send_request(&request)?;
+        let response = Self::await_response(&rx)?;
 
         let session_id = response
             .result
@@ -203,39 +902,139 @@ impl CodexAdapter {
         Ok(session_id)
     }
 
-    /// Start the MCP server for psychology analysis
-    fn start_mcp_server(&self) -> Result<()> {
-        let project_root = std::env!("CARGO_MANIFEST_DIR");
-        let mcp_server_path = format!("{}/mcp-servers/mcp_codex_psychology", project_root);
+    /// Builds the `mcpServers` array `session/new` expects from the
+    /// registered server configs (codex-acp only supports the `http`
+    /// transport type, not `sse`, so an `Sse` registration is sent the same
+    /// way `Http` is - the distinction matters for the readiness probe, not
+    /// the wire format).
+    fn mcp_servers_json(&self) -> serde_json::Value {
+        let servers = self.mcp_servers.lock().unwrap();
+        let entries: Vec<serde_json::Value> = servers
+            .iter()
+            .map(|config| match &config.transport {
+                McpServerTransport::Http { url, headers } | McpServerTransport::Sse { url, headers } => json!({
+                    "name": config.name,
+                    "type": "http",
+                    "url": url,
+                    "headers": headers.iter().map(|(name, value)| json!({ "name": name, "value": value })).collect::<Vec<_>>(),
+                }),
+                McpServerTransport::Stdio { command, args } => json!({
+                    "name": config.name,
+                    "type": "stdio",
+                    "command": command,
+                    "args": args,
+                }),
+            })
+            .collect();
+        json!(entries)
+    }
+
+    /// Bootstraps every registered server that has a local process to start,
+    /// then waits for each of them to become reachable (polling instead of a
+    /// fixed sleep, so a server that's already warm doesn't cost any extra
+    /// startup time, and a slow one still gets a real chance to come up).
+    fn start_mcp_servers(&self) -> Result<()> {
+        let servers = self.mcp_servers.lock().unwrap().clone();
 
-        // Check if MCP server exists
-        if !std::path::Path::new(&mcp_server_path).exists() {
-            eprintln!("MCP server not found at: {}", mcp_server_path);
+        for config in &servers {
+            if let Some(bootstrap) = &config.bootstrap {
+                self.bootstrap_mcp_server(&config.name, bootstrap)?;
+            }
+
+            if let McpServerTransport::Http { url, .. } | McpServerTransport::Sse { url, .. } = &config.transport {
+                Self::wait_for_mcp_server_ready(&config.name, url, config.readiness_timeout)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Starts `bootstrap`'s local process (creating and populating a Python
+    /// venv first, if `venv_setup` is set and one doesn't already exist yet),
+    /// tracking the child so it gets killed when the adapter is dropped. A
+    /// no-op if `name` is already tracked as running - `create_session` calls
+    /// this on every reconnect, and without this check each dropped
+    /// connection would spawn another copy of every bootstrap server.
+    fn bootstrap_mcp_server(&self, name: &str, bootstrap: &McpServerBootstrap) -> Result<()> {
+        if self.running_mcp_servers.lock().unwrap().contains(name) {
+            eprintln!("MCP server '{}' is already running, skipping bootstrap", name);
+            return Ok(());
+        }
+
+        if !std::path::Path::new(&bootstrap.working_dir).exists() {
+            eprintln!("MCP server '{}' not found at: {}", name, bootstrap.working_dir);
             return Ok(()); // Not an error - just no MCP
         }
 
-        eprintln!("Starting MCP server at: {}", mcp_server_path);
+        eprintln!("Starting MCP server '{}' at: {}", name, bootstrap.working_dir);
+
+        // With a venv, `command` is a script run through the venv's python
+        // rather than an executable in its own right, so it becomes the
+        // first argument instead of the program name.
+        let (program, args): (String, Vec<String>) = if bootstrap.venv_setup {
+            let python = self.ensure_venv_python(&bootstrap.working_dir)?;
+            let mut args = vec![bootstrap.command.clone()];
+            args.extend(bootstrap.args.clone());
+            (python, args)
+        } else {
+            (bootstrap.command.clone(), bootstrap.args.clone())
+        };
+
+        let mut child = Command::new(&program)
+            .args(&args)
+            .current_dir(&bootstrap.working_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to start MCP server '{}'", name))?;
 
-        // Check if venv exists, create if it doesn't
-        let venv_path = format!("{}/venv", mcp_server_path);
+        let server_name = name.to_string();
+        if let Some(stdout) = child.stdout.take() {
+            let server_name = server_name.clone();
+            std::thread::spawn(move || {
+                let reader = BufReader::new(stdout);
+                for line in reader.lines().flatten() {
+                    eprintln!("[MCP/{}] {}", server_name, line);
+                }
+            });
+        }
+        if let Some(stderr) = child.stderr.take() {
+            std::thread::spawn(move || {
+                let reader = BufReader::new(stderr);
+                for line in reader.lines().flatten() {
+                    eprintln!("[MCP/{} stderr] {}", server_name, line);
+                }
+            });
+        }
+
+        self.mcp_server_processes.lock().unwrap().push(child);
+        self.running_mcp_servers.lock().unwrap().insert(name.to_string());
+
+        Ok(())
+    }
+
+    /// Returns the venv's Python interpreter path in `working_dir`, creating
+    /// the venv and installing `requirements.txt` into it first if it
+    /// doesn't exist yet.
+    fn ensure_venv_python(&self, working_dir: &str) -> Result<String> {
+        let venv_path = format!("{}/venv", working_dir);
         let venv_python = format!("{}/bin/python3", venv_path);
 
         if !std::path::Path::new(&venv_path).exists() {
-            eprintln!("Creating Python virtual environment...");
+            eprintln!("Creating Python virtual environment in {}...", working_dir);
 
-            // Find system python3
             let system_python = which::which("python3")
                 .or_else(|_| which::which("python"))
                 .map_err(|_| anyhow!("Python not found. Please install Python 3"))?
                 .to_string_lossy()
                 .to_string();
 
-            // Create venv
             let venv_status = Command::new(&system_python)
                 .arg("-m")
                 .arg("venv")
                 .arg("venv")
-                .current_dir(&mcp_server_path)
+                .current_dir(working_dir)
                 .status()
                 .context("Failed to create virtual environment")?;
 
@@ -243,28 +1042,22 @@ impl CodexAdapter {
                 return Err(anyhow!("Failed to create virtual environment"));
             }
 
-            eprintln!("✅ Virtual environment created successfully");
+            eprintln!("Virtual environment created successfully");
         }
 
-        // Use venv python
-        let python_path = if std::path::Path::new(&venv_python).exists() {
-            venv_python
-        } else {
+        if !std::path::Path::new(&venv_python).exists() {
             return Err(anyhow!("Virtual environment was not created properly"));
-        };
-
-        eprintln!("Using Python: {}", python_path);
+        }
 
-        // Install dependencies
         eprintln!("Installing MCP server dependencies...");
-        let install_status = Command::new(&python_path)
+        let install_status = Command::new(&venv_python)
             .arg("-m")
             .arg("pip")
             .arg("install")
             .arg("-q")
             .arg("-r")
             .arg("requirements.txt")
-            .current_dir(&mcp_server_path)
+            .current_dir(working_dir)
             .status()
             .context("Failed to install MCP dependencies")?;
 
@@ -272,49 +1065,55 @@ impl CodexAdapter {
             return Err(anyhow!("Failed to install MCP server dependencies"));
         }
 
-        eprintln!("✅ MCP server dependencies installed successfully");
+        eprintln!("MCP server dependencies installed successfully");
 
-        // Start the MCP server with SSE transport
-        let mut child = Command::new(&python_path)
-            .arg("run_sse_server.py")
-            .current_dir(&mcp_server_path)
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .context("Failed to start MCP server")?;
+        Ok(venv_python)
+    }
 
-        // Spawn thread to consume stdout
-        if let Some(stdout) = child.stdout.take() {
-            std::thread::spawn(move || {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines().flatten() {
-                    eprintln!("[MCP] {}", line);
-                }
-            });
-        }
+    /// Polls `url`'s host and port until a TCP connection succeeds or
+    /// `timeout` elapses, replacing a fixed startup sleep with a real
+    /// readiness check.
+    fn wait_for_mcp_server_ready(name: &str, url: &str, timeout: Duration) -> Result<()> {
+        let Some(addr) = Self::host_port_from_url(url) else {
+            // Not a host:port we can dial directly (e.g. a remote HTTPS
+            // endpoint on a standard port behind a gateway) - nothing useful
+            // to poll, so trust it's already up.
+            return Ok(());
+        };
 
-        // Spawn thread to consume stderr
-        if let Some(stderr) = child.stderr.take() {
-            std::thread::spawn(move || {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines().flatten() {
-                    eprintln!("[MCP stderr] {}", line);
-                }
-            });
-        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            if TcpStream::connect_timeout(&addr, Duration::from_millis(500)).is_ok() {
+                eprintln!("MCP server '{}' is ready at {}", name, url);
+                return Ok(());
+            }
 
-        *self.mcp_server_process.lock().unwrap() = Some(child);
+            if Instant::now() >= deadline {
+                return Err(anyhow!(
+                    "MCP server '{}' did not become ready at {} within {:?}",
+                    name,
+                    url,
+                    timeout
+                ));
+            }
 
-        // Wait a bit for server to start
-        std::thread::sleep(std::time::Duration::from_secs(2));
+            std::thread::sleep(Duration::from_millis(200));
+        }
+    }
 
-        eprintln!("MCP server started successfully on port 52848");
-        Ok(())
+    /// Extracts a `host:port` socket address from an `http(s)://` URL, for
+    /// URLs that name a loopback/local address we can actually dial directly
+    /// (`127.0.0.1:52848` in `http://127.0.0.1:52848/mcp`). Returns `None`
+    /// for anything else (hostnames needing DNS, implicit default ports),
+    /// since this is only meant to cover locally-bootstrapped servers.
+    fn host_port_from_url(url: &str) -> Option<std::net::SocketAddr> {
+        let rest = url.strip_prefix("http://").or_else(|| url.strip_prefix("https://"))?;
+        let authority = rest.split('/').next()?;
+        authority.parse().ok()
     }
 
     /// Send a message and stream responses via callback
-    pub fn send_message<F>(&self, message: String, mut callback: F) -> Result<()>
+    fn send_message<F>(&self, message: String, mut callback: F) -> Result<()>
     where
         F: FnMut(StreamEvent),
     {
@@ -347,11 +1146,25 @@ impl CodexAdapter {
             }),
         };
 
-        self.send_request(&request)?;
+        // `session/prompt` is the one call that streams notifications while
+        // it's in flight, so its final response and its notification stream
+        // share one channel here: the reader thread routes both the matching
+        // `id` and every notification seen while this is the active session
+        // call onto `tx`, and this loop drains them in arrival order until it
+        // sees the response for `id`.
+        let (tx, rx) = mpsc::channel();
+        {
+            let mut routing = self.routing.lock().unwrap();
+            routing.pending.insert(id, tx.clone());
+            routing.notifications = Some(tx);
+        }
+        self.write_request(&request)?;
 
         // Read streaming responses
         loop {
-            let response = self.read_response()?;
+            let response = rx.recv()
+                .map_err(|_| anyhow!("codex-acp reader thread stopped before streaming finished"))?
+                .map_err(|e| anyhow!(e))?;
 
             // Handle notifications (streaming updates)
             if let Some(method) = response.method {
@@ -401,13 +1214,17 @@ impl CodexAdapter {
                                                     }
                                                 }
 
+                                                let mcp_server = self.mcp_servers.lock().unwrap().iter()
+                                                    .find(|config| title.contains(config.name.as_str()) || kind.contains(config.name.as_str()))
+                                                    .and_then(|config| McpServerType::from_server_name(&config.name));
+
                                                 callback(StreamEvent::ToolCall(ToolCallEvent {
                                                     tool_call_id: tool_call_id.to_string(),
                                                     title,
                                                     kind,
                                                     status,
                                                     locations,
-                                                    mcp_server: Some(crate::types::McpServerType::CodexPsychology), // TODO: Parse from tool name
+                                                    mcp_server,
                                                 }));
                                             }
                                         }
@@ -461,6 +1278,37 @@ impl CodexAdapter {
                         eprintln!("Stream complete");
                         break;
                     }
+                    "client/file_write" => {
+                        if let Some(params) = response.params {
+                            let path = params.get("path").and_then(|p| p.as_str()).unwrap_or("").to_string();
+                            let bytes_written = params.get("bytesWritten").and_then(|b| b.as_u64()).unwrap_or(0) as usize;
+                            callback(StreamEvent::FileWrite(FileWriteEvent { path, bytes_written }));
+                        }
+                    }
+                    "client/terminal_output" => {
+                        if let Some(params) = response.params {
+                            let terminal_id = params.get("terminalId").and_then(|t| t.as_str()).unwrap_or("").to_string();
+                            let output = params.get("output").and_then(|o| o.as_str()).unwrap_or("").to_string();
+                            callback(StreamEvent::TerminalOutput(TerminalOutputEvent { terminal_id, output }));
+                        }
+                    }
+                    "client/reconnecting" => {
+                        if let Some(params) = response.params {
+                            let attempt = params.get("attempt").and_then(|a| a.as_u64()).unwrap_or(0) as u32;
+                            let max_attempts = params.get("maxAttempts").and_then(|a| a.as_u64()).unwrap_or(0) as u32;
+                            callback(StreamEvent::ConnectionReconnecting(ReconnectEvent { attempt, max_attempts }));
+                        }
+                    }
+                    "client/reconnected" => {
+                        callback(StreamEvent::ConnectionReconnected);
+                    }
+                    "client/connection_failed" => {
+                        let reason = response.params
+                            .and_then(|params| params.get("reason").and_then(|r| r.as_str()).map(str::to_string))
+                            .unwrap_or_else(|| "codex-acp connection lost".to_string());
+                        callback(StreamEvent::ConnectionFailed(reason.clone()));
+                        return Err(anyhow!(reason));
+                    }
                     _ => {
                         eprintln!("Unknown method: {}", method);
                     }
@@ -477,38 +1325,38 @@ impl CodexAdapter {
         Ok(())
     }
 
-    fn send_request(&self, request: &JsonRpcRequest) -> Result<()> {
+    /// Registers `request.id` in the pending map before writing to stdin, so
+    /// the reader thread can never route a matching response before the
+    /// caller is ready to receive it, then returns a receiver the caller owns
+    /// exclusively - no other in-flight call can steal its reply.
+    fn send_request(&self, request: &JsonRpcRequest) -> Result<mpsc::Receiver<ReaderResult>> {
+        let (tx, rx) = mpsc::channel();
+        self.routing.lock().unwrap().pending.insert(request.id, tx);
+        self.write_request(request)?;
+        Ok(rx)
+    }
+
+    /// Serializes `request` and writes it through the transport. Split out
+    /// from `send_request` so `send_message` can register its id and
+    /// notification channel together before the bytes hit the wire.
+    fn write_request(&self, request: &JsonRpcRequest) -> Result<()> {
         let json = serde_json::to_string(request)?;
-        let mut stdin = self.stdin.lock().unwrap();
-        let stdin = stdin.as_mut()
-            .ok_or_else(|| anyhow!("No stdin available"))?;
+        let transport = self.transport.lock().unwrap().clone()
+            .ok_or_else(|| anyhow!("No transport available - call spawn() or connect() first"))?;
 
-        writeln!(stdin, "{}", json)?;
-        stdin.flush()?;
+        transport.send_line(&json)?;
 
         eprintln!("-> {}", json);
 
         Ok(())
     }
 
-    fn read_response(&self) -> Result<JsonRpcResponse> {
-        let mut stdout = self.stdout.lock().unwrap();
-        let stdout = stdout.as_mut()
-            .ok_or_else(|| anyhow!("No stdout available"))?;
-
-        let mut line = String::new();
-        stdout.read_line(&mut line)?;
-
-        if line.is_empty() {
-            return Err(anyhow!("EOF reached"));
-        }
-
-        eprintln!("<- {}", line.trim());
-
-        let response: JsonRpcResponse = serde_json::from_str(&line)
-            .context("Failed to parse JSON-RPC response")?;
-
-        Ok(response)
+    /// Blocks until the reader thread completes `rx` with the matching
+    /// response, or reports that the connection closed while waiting.
+    fn await_response(rx: &mpsc::Receiver<ReaderResult>) -> Result<JsonRpcResponse> {
+        rx.recv()
+            .map_err(|_| anyhow!("codex-acp reader thread stopped before a response arrived"))?
+            .map_err(|e| anyhow!(e))
     }
 
     fn next_id(&self) -> u64 {
@@ -538,19 +1386,54 @@ impl CodexAdapter {
     }
 }
 
-impl Drop for CodexAdapter {
-    fn drop(&mut self) {
-        if let Some(mut child) = self.process.lock().unwrap().take() {
-            let _ = child.kill();
-            let _ = child.wait();
-            eprintln!("codex-acp process terminated");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drives the same disconnect→reconnect path `run_reader` takes on a
+    /// transport error, against the channel `send_message` sets up before
+    /// writing its request, and asserts the notification stream reports
+    /// `client/reconnecting` (and eventually a terminal connection event)
+    /// rather than the channel just closing with a raw error - the bug fixed
+    /// here was `close_all_pending` tearing down the notification sender
+    /// before `reconnect` ever got a chance to report anything on it.
+    #[test]
+    fn send_message_observes_reconnect_events_instead_of_a_raw_error() {
+        let mut handle = CodexAdapterHandle::new();
+        handle.supervisor_config = SupervisorConfig {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        {
+            let mut routing = handle.routing.lock().unwrap();
+            routing.pending.insert(1, tx.clone());
+            routing.notifications = Some(tx);
         }
 
-        // Kill the MCP server subprocess
-        if let Some(mut mcp_child) = self.mcp_server_process.lock().unwrap().take() {
-            let _ = mcp_child.kill();
-            let _ = mcp_child.wait();
-            eprintln!("MCP server stopped");
+        let reconnecting_handle = handle.clone();
+        std::thread::spawn(move || {
+            CodexAdapterHandle::disconnected(&reconnecting_handle, "simulated read failure".to_string());
+        });
+
+        let first = rx.recv().unwrap().expect("expected a notification, not a terminal error");
+        assert_eq!(first.method.as_deref(), Some("client/reconnecting"));
+
+        // No real `codex-acp` binary to spawn in a test environment, so every
+        // attempt fails and the sequence ends in `client/connection_failed`
+        // rather than `client/reconnected` - the point under test is that it's
+        // observed as a connection-event notification, not a bare channel close.
+        loop {
+            match rx.recv().unwrap() {
+                Ok(response) if response.method.as_deref() == Some("client/reconnecting") => continue,
+                Ok(response) => {
+                    assert_eq!(response.method.as_deref(), Some("client/connection_failed"));
+                    break;
+                }
+                Err(_) => panic!("notification channel closed before a terminal connection event was sent"),
+            }
         }
     }
 }
@@ -0,0 +1,345 @@
+// UI-agnostic session controller - owns repo selection, enrichment, and chat state
+// plus the logic that drives it (git analysis wiring, system-prompt construction,
+// stream-buffer bookkeeping). Frontends (the GPUI `CodexView`, the `tui` ratatui
+// frontend) each own their own widget/input state and call into this struct's
+// mutating methods in response to user actions, so neither has to re-implement
+// enrichment or streaming from scratch.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::runtime::Handle;
+
+use crate::codex_adapter::CodexAdapter;
+use crate::forge::{self, ForgeConfig};
+use crate::git_analyzer::GitAnalyzer;
+use crate::project_context::ProjectContext;
+use crate::semantic_index::{self, RetrievedChunk};
+use crate::storage::{Observation, Storage};
+use crate::types::{self, AppState, GitAnalysis, GitPattern, Message, TimelineEvent};
+use crate::webhook::{UserNotification, WebhookDispatcher};
+
+/// How many chunks to retrieve per psychology probe in `gather_semantic_evidence`.
+const EVIDENCE_TOP_K: usize = 3;
+
+const HIGH_SEVERITY_THRESHOLD: f32 = 0.7;
+
+/// Core session state: repo selection, enrichment results, chat history, and
+/// in-flight streaming buffers.
+pub struct CodexController {
+    pub app_state: AppState,
+    pub selected_repo: Option<String>,
+    pub messages: Vec<Message>,
+    pub timeline_events: Vec<TimelineEvent>,
+    pub lifecycle_events: Vec<types::LifecycleEvent>,
+    pub storage: Option<Arc<Storage>>,
+    pub tokio_handle: Handle,
+    pub codex_adapter: Option<Arc<CodexAdapter>>,
+    pub is_loading: bool,
+    pub error_message: Option<String>,
+    // Streaming state (temporary until event completes)
+    pub current_thought_buffer: String,
+    pub current_message_buffer: String,
+    pub active_tool_calls: HashMap<String, (types::ToolCallEvent, String)>, // (event, output)
+    // Enrichment timer
+    pub enrichment_start_time: Option<std::time::Instant>,
+    pub enrichment_elapsed: f32, // seconds
+    // Set when `CODEXD_WEBHOOK_URL`/`CODEXD_WEBHOOK_SECRET` are configured, so flagged
+    // patterns get POSTed to a downstream consumer (Slack, Gitea, CI, ...).
+    pub webhook: Option<Arc<WebhookDispatcher>>,
+    // Optional "connect a forge" step - a personal access token for the repo's
+    // `origin` remote, used to pull PR collaboration patterns alongside the local
+    // commit patterns. `None` means enrichment stays local-only.
+    pub forge_token: Option<String>,
+    // Repos found under a dropped/browsed parent directory by `repo_discovery`,
+    // offered on page 1 as a batch-analysis candidate list.
+    pub discovered_repos: Vec<String>,
+}
+
+impl CodexController {
+    pub fn new(
+        tokio_handle: Handle,
+        storage: Option<Arc<Storage>>,
+        webhook: Option<Arc<WebhookDispatcher>>,
+    ) -> Self {
+        Self {
+            app_state: AppState::AwaitingRepoSelection,
+            selected_repo: None,
+            messages: Vec::new(),
+            timeline_events: Vec::new(),
+            lifecycle_events: Vec::new(),
+            storage,
+            tokio_handle,
+            codex_adapter: None,
+            is_loading: false,
+            error_message: None,
+            current_thought_buffer: String::new(),
+            current_message_buffer: String::new(),
+            active_tool_calls: HashMap::new(),
+            enrichment_start_time: None,
+            enrichment_elapsed: 0.0,
+            webhook,
+            forge_token: None,
+            discovered_repos: Vec::new(),
+        }
+    }
+
+    /// Resets session state for a freshly selected repository.
+    pub fn begin_enrichment(&mut self, repo_path: String) {
+        self.selected_repo = Some(repo_path);
+        self.app_state = AppState::Enriching;
+        self.is_loading = true;
+        self.lifecycle_events.clear();
+        self.messages.clear(); // Clear old messages from previous runs
+        self.lifecycle_events.push(types::LifecycleEvent::running("Scanning git history".to_string()));
+    }
+
+    /// Notifies the configured webhook (if any) about flagged patterns and overall
+    /// completion. Called once git analysis (plus any forge patterns) finishes.
+    pub fn notify_webhook(&self, repo_path: &str, analysis: &GitAnalysis) {
+        let Some(webhook) = &self.webhook else { return };
+
+        for pattern in &analysis.patterns {
+            if pattern.severity >= HIGH_SEVERITY_THRESHOLD {
+                let _ = webhook.notify(UserNotification::HighSeverityPattern {
+                    repo_path: repo_path.to_string(),
+                    title: pattern.title.clone(),
+                    description: pattern.description.clone(),
+                    severity: pattern.severity,
+                });
+            }
+        }
+        let _ = webhook.notify(UserNotification::EnrichmentComplete {
+            repo_path: repo_path.to_string(),
+            commit_count: analysis.total_commits_analyzed,
+            pattern_count: analysis.patterns.len(),
+        });
+    }
+
+    /// Persists this run's analysis for longitudinal diffing, returning the prior
+    /// session's observation (if any) so the caller can build a "what changed"
+    /// section before the new one overwrites it.
+    pub fn record_observation(&self, repo_path: &str, analysis: &GitAnalysis) -> Option<Observation> {
+        let storage = self.storage.as_ref()?;
+        let prior = storage.load_latest_analysis(repo_path).ok().flatten();
+
+        let observation = format!(
+            "Analyzed {} commits, severity {:.2}",
+            analysis.total_commits_analyzed, analysis.severity
+        );
+        let _ = storage.save_observation(repo_path, &observation, analysis);
+
+        prior
+    }
+
+    /// Builds the full system prompt for a freshly analyzed repository, folding in
+    /// manifest context, a session-over-session diff when prior history exists, and
+    /// concrete commits retrieved from the semantic index (if any) so the model's
+    /// observations can cite real commits instead of only aggregate patterns.
+    pub fn build_system_prompt(
+        repo_path: &str,
+        analysis: &GitAnalysis,
+        prior: Option<&Observation>,
+        evidence: &[RetrievedChunk],
+    ) -> String {
+        let patterns_summary = if analysis.patterns.is_empty() {
+            "No significant behavioral patterns detected in git history.".to_string()
+        } else {
+            analysis.patterns.iter()
+                .map(|p| format!("• {}: {}", p.title, p.description))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        // Manifest-only project context (name/description/dependencies) - never the
+        // source files themselves, so this doesn't cross into code review.
+        let manifest_section = ProjectContext::detect(repo_path)
+            .map(|ctx| ctx.to_prompt_section())
+            .unwrap_or_default();
+
+        // Longitudinal diff against the prior session's analysis, if any - lets the
+        // system prompt cite concrete deltas ("night-commit rate dropped from 62% to
+        // 41%") instead of re-discovering the same patterns from scratch every run.
+        let changes_section = prior
+            .map(|prior| diff_patterns(&prior.patterns(), &analysis.patterns))
+            .filter(|diff| !diff.is_empty())
+            .map(|diff| format!("## CHANGES SINCE LAST SESSION\n\n{}\n\n", diff))
+            .unwrap_or_default();
+
+        // Commits the semantic index judged most similar to common developer
+        // psychology phrasings (frustration, perfectionism, context-switching), so
+        // Phase 3 observations can cite a specific sha/file instead of "47 commits
+        // at night" alone.
+        let evidence_section = if evidence.is_empty() {
+            String::new()
+        } else {
+            let lines = evidence.iter()
+                .map(|chunk| format!(
+                    "• commit {} ({}): {}",
+                    &chunk.sha[..chunk.sha.len().min(8)],
+                    if chunk.file_path.is_empty() { "message" } else { &chunk.file_path },
+                    chunk.text.lines().next().unwrap_or_default(),
+                ))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("## RETRIEVED COMMIT EVIDENCE\n\n{}\n\n", lines)
+        };
+
+        format!(
+            "You are a developer psychologist practicing evidence-based conversational archaeology.\n\n\
+             ## GIT COMMIT PATTERNS (from {} commits analyzed, severity {:.1}/1.0)\n\n\
+             {}\n\n\
+             {}\
+             {}\
+             {}\
+             ## YOUR MISSION: GUIDE USERS TO ENRICH THEIR PROJECT\n\n\
+             You have MCP tools to analyze git patterns. Use them to:\n\
+             1. Surface blindspots users can't see themselves\n\
+             2. Ask questions that make them reflect deeply\n\
+             3. Guide them toward actionable improvements\n\
+             4. Build longitudinal understanding across sessions\n\n\
+             ## CONVERSATION STRATEGY (Socratic Guidance)\n\n\
+             **Phase 1: Discovery** (Current - gather context)\n\
+             - Ask about: project goals, team structure, customer, timeline\n\
+             - Use their answers to understand MOTIVATION and CONSTRAINTS\n\
+             - Build rapport through genuine curiosity\n\
+             - Listen for what they DON'T say\n\n\
+             **Phase 2: Investigation** (use MCP tools)\n\
+             When you have context, use tools to dig deeper:\n\
+             - `analyze_commit_patterns` - find commitment issues\n\
+             - `analyze_message_language` - detect minimizing/defensive patterns\n\
+             - `compare_message_vs_diff` - spot self-deception\n\
+             - `get_temporal_patterns` - reveal stress/overwork\n\
+             - `get_repo_context` - access memory from past sessions\n\n\
+             **Phase 3: Observation** (synthesize evidence)\n\
+             Create a 3-4 sentence observation:\n\
+             1. Cite EXACT git numbers (\"47 commits at night = 62%\")\n\
+             2. Connect to their stated goals (\"but you said X...\")\n\
+             3. Name the pattern (\"This suggests Y anti-pattern\")\n\
+             4. Ask ONE pointed question about the blindspot\n\n\
+             **Phase 4: Guidance** (lead toward action)\n\
+             Based on their response:\n\
+             - Validate their awareness\n\
+             - Suggest concrete experiments\n\
+             - Use `flag_repo_issue` to track the pattern\n\
+             - Offer to check back next session\n\n\
+             ## ABSOLUTE RULES\n\n\
+             - DO NOT read, analyze, or reference source code files\n\
+             - DO NOT do code review or technical assessment\n\
+             - Focus on BEHAVIOR patterns, not code quality\n\
+             - Use EXACT numbers from git data (never approximate)\n\
+             - Be conversational and empathetic - therapist, not linter\n\
+             - Each question should make them think deeper about their project\n\n\
+             **Your goal: Guide them to insights they'd never find alone. Make them WANT to share more about their project.**",
+            analysis.total_commits_analyzed,
+            analysis.severity,
+            patterns_summary,
+            manifest_section,
+            changes_section,
+            evidence_section,
+        )
+    }
+
+    /// Runs git analysis and, if a forge is connected, folds in PR collaboration
+    /// patterns - the per-repo unit of work shared by the single-repo flow
+    /// (`CodexView::on_repo_selected`) and the multi-repo batch flow
+    /// (`CodexView::on_analyze_all_clicked`).
+    pub async fn analyze_repo_with_forge(
+        repo_path: &str,
+        forge_config: Option<ForgeConfig>,
+        progress: impl Fn(String, f32) + Send + Sync + 'static,
+    ) -> anyhow::Result<GitAnalysis> {
+        let mut analysis = GitAnalyzer::analyze(repo_path, progress).await?;
+
+        if let Some(config) = forge_config {
+            match forge::fetch_pr_metrics(&config).await {
+                Ok(metrics) => analysis.patterns.extend(forge::collaboration_patterns(&metrics)),
+                Err(e) => eprintln!("Forge metrics fetch failed: {}", e),
+            }
+        }
+
+        Ok(analysis)
+    }
+
+    /// Incrementally embeds `repo_path`'s commit history and retrieves the chunks
+    /// most similar to the psychology probe queries, for `build_system_prompt`'s
+    /// evidence section. Indexing failures (e.g. no local git binary/repo access)
+    /// degrade to no evidence rather than failing the whole enrichment flow, since
+    /// this is a citation aid, not a required part of the analysis.
+    pub async fn gather_semantic_evidence(
+        repo_path: &str,
+        progress: impl Fn(String, f32) + Send + Sync + 'static,
+    ) -> Vec<RetrievedChunk> {
+        match semantic_index::index_and_retrieve(repo_path, EVIDENCE_TOP_K, progress).await {
+            Ok(evidence) => evidence,
+            Err(e) => {
+                eprintln!("Semantic index failed for {}: {}", repo_path, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// The discovery greeting shown once a session is created, tailored to whether
+    /// any behavioral patterns were found.
+    pub fn discovery_greeting(analysis: &GitAnalysis) -> String {
+        let pattern_count = analysis.patterns.len();
+        let commit_count = analysis.total_commits_analyzed;
+
+        if pattern_count > 0 {
+            let top_pattern = &analysis.patterns[0];
+            format!(
+                "## 🔍 Analysis Complete\n\n\
+                 I've analyzed **{} commits** and discovered **{} behavioral patterns**.\n\n\
+                 Most notable: *{}*\n\n\
+                 Before I share my observations, I'd like to understand the context.\n\n\
+                 **Tell me about this project:**\n\
+                 - What are you building?\n\
+                 - Who's working on it?\n\
+                 - What's the goal?",
+                commit_count, pattern_count, top_pattern.title
+            )
+        } else {
+            format!(
+                "## 👋 Let's Explore Your Code\n\n\
+                 I've analyzed **{} commits** from your repository.\n\n\
+                 To give you meaningful insights, I need to understand:\n\n\
+                 **What is this project?** Tell me about what you're building and who it's for.",
+                commit_count
+            )
+        }
+    }
+}
+
+/// Compares two analysis runs' patterns by `pattern_type`, surfacing severity deltas for
+/// patterns seen in both sessions plus call-outs for patterns that newly appeared or
+/// disappeared, so the system prompt can cite concrete session-over-session movement.
+pub fn diff_patterns(prior: &[GitPattern], current: &[GitPattern]) -> String {
+    const NOTABLE_DELTA: f32 = 0.01;
+
+    let mut lines = Vec::new();
+
+    for pattern in current {
+        match prior.iter().find(|p| p.pattern_type == pattern.pattern_type) {
+            Some(before) => {
+                let delta = pattern.severity - before.severity;
+                if delta.abs() >= NOTABLE_DELTA {
+                    lines.push(format!(
+                        "• {} severity {} from {:.0}% to {:.0}%",
+                        pattern.title,
+                        if delta < 0.0 { "dropped" } else { "rose" },
+                        before.severity * 100.0,
+                        pattern.severity * 100.0,
+                    ));
+                }
+            }
+            None => lines.push(format!("• New pattern since last session: {}", pattern.title)),
+        }
+    }
+
+    for pattern in prior {
+        if !current.iter().any(|p| p.pattern_type == pattern.pattern_type) {
+            lines.push(format!("• No longer observed: {}", pattern.title));
+        }
+    }
+
+    lines.join("\n")
+}
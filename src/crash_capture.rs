@@ -0,0 +1,179 @@
+// Crash-capture subsystem - installs a panic hook that turns an opaque panic into a
+// `TimelineEvent::CrashReport` with demangled backtrace frames, so a tool/MCP failure
+// is actionable diagnostics tied to the exact point in the session timeline instead of
+// a bare string on a `LifecycleEvent::Failed`.
+
+use std::io::Write;
+use std::net::TcpStream;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use crate::types::{now_timestamp, TimelineEvent};
+
+/// Where a captured crash report should be written. Opt-in: capture only uploads
+/// anywhere once a sink has been configured via `install`.
+#[derive(Debug, Clone)]
+pub enum CrashSink {
+    /// Append newline-delimited JSON reports to a local file.
+    LocalFile(PathBuf),
+    /// POST each report as JSON to an HTTP endpoint (plain HTTP, `host:port/path`).
+    HttpEndpoint { endpoint: String, retention_hint: Option<String> },
+}
+
+static ON_CRASH: OnceLock<Mutex<Box<dyn Fn(TimelineEvent) + Send>>> = OnceLock::new();
+static SINK: OnceLock<CrashSink> = OnceLock::new();
+
+/// Installs the panic hook. `on_crash` is invoked with the resulting
+/// `TimelineEvent::CrashReport` so it can be pushed onto the live session timeline;
+/// `sink`, if given, additionally persists the report outside the process.
+pub fn install<F>(on_crash: F, sink: Option<CrashSink>)
+where
+    F: Fn(TimelineEvent) + Send + 'static,
+{
+    let _ = ON_CRASH.set(Mutex::new(Box::new(on_crash)));
+    if let Some(sink) = sink {
+        let _ = SINK.set(sink);
+    }
+
+    std::panic::set_hook(Box::new(handle_panic));
+}
+
+/// Associates a tool name with the current thread's next captured panic, so the
+/// resulting `CrashReport` can say which tool call was running when it happened.
+pub fn set_current_tool(tool_name: Option<String>) {
+    CURRENT_TOOL.with(|cell| *cell.borrow_mut() = tool_name);
+}
+
+thread_local! {
+    static CURRENT_TOOL: std::cell::RefCell<Option<String>> = std::cell::RefCell::new(None);
+}
+
+fn handle_panic(info: &PanicHookInfo) {
+    let message = panic_message(info);
+    let demangled_frames = capture_demangled_backtrace();
+    let tool_name = CURRENT_TOOL.with(|cell| cell.borrow().clone());
+    let timestamp = now_timestamp();
+
+    let report = TimelineEvent::CrashReport {
+        message,
+        demangled_frames,
+        tool_name,
+        timestamp,
+    };
+
+    if let Some(on_crash) = ON_CRASH.get() {
+        if let Ok(on_crash) = on_crash.lock() {
+            on_crash(report.clone());
+        }
+    }
+
+    if let Some(sink) = SINK.get() {
+        if let Err(err) = persist(sink, &report) {
+            eprintln!("Failed to persist crash report: {}", err);
+        }
+    }
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked with a non-string payload".to_string()
+    }
+}
+
+/// Captures the current backtrace and runs each frame's mangled symbol through
+/// `rustc_demangle` to produce readable names (`foo::bar::Baz::qux` instead of
+/// `_ZN3foo3bar3Baz3qux17h...E`).
+fn capture_demangled_backtrace() -> Vec<String> {
+    let backtrace = std::backtrace::Backtrace::force_capture();
+    format!("{}", backtrace)
+        .lines()
+        .map(|line| match frame_header(line) {
+            Some((number, symbol)) => format!("{}: {}", number, rustc_demangle::demangle(symbol)),
+            None => line.to_string(),
+        })
+        .collect()
+}
+
+/// Splits a `Backtrace`'s `"  N: symbol"` frame-header line into its frame number
+/// and symbol, or `None` for any other line (e.g. the `"             at file:line:col"`
+/// location line that follows each header). `Backtrace`'s `Display` never uses
+/// `" - "` as a separator - that was this function's previous (wrong) assumption,
+/// which meant `rustc_demangle::demangle` was never actually reached.
+fn frame_header(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    let (number, symbol) = trimmed.split_once(':')?;
+    let symbol = symbol.trim_start();
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) && !symbol.is_empty())
+        .then_some((number, symbol))
+}
+
+fn persist(sink: &CrashSink, report: &TimelineEvent) -> anyhow::Result<()> {
+    match sink {
+        CrashSink::LocalFile(path) => {
+            let json = serde_json::to_string(report)?;
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?;
+            writeln!(file, "{}", json)?;
+            Ok(())
+        }
+        CrashSink::HttpEndpoint { endpoint, retention_hint } => {
+            let (authority, path) = endpoint.split_once('/').unwrap_or((endpoint.as_str(), ""));
+            let body = serde_json::to_vec(report)?;
+            let mut stream = TcpStream::connect(authority)?;
+            let retention_header = retention_hint
+                .as_ref()
+                .map(|hint| format!("X-Retention-Hint: {}\r\n", hint))
+                .unwrap_or_default();
+            let request = format!(
+                "POST /{} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+                path,
+                authority,
+                body.len(),
+                retention_header,
+            );
+            stream.write_all(request.as_bytes())?;
+            stream.write_all(&body)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_header_matches_the_real_backtrace_display_format() {
+        assert_eq!(
+            frame_header("   3: codex_d::crash_capture::capture_demangled_backtrace"),
+            Some(("3", "codex_d::crash_capture::capture_demangled_backtrace")),
+        );
+        assert_eq!(frame_header("             at src/crash_capture.rs:92:5"), None);
+        assert_eq!(frame_header(""), None);
+    }
+
+    #[test]
+    fn captured_backtrace_contains_a_demangled_not_raw_mangled_symbol() {
+        let frames = capture_demangled_backtrace();
+        assert!(!frames.is_empty());
+
+        let symbols: Vec<&str> = frames.iter()
+            .filter_map(|line| frame_header(line).map(|(_, symbol)| symbol))
+            .collect();
+        assert!(!symbols.is_empty(), "expected at least one frame header, got: {:?}", frames);
+        assert!(
+            symbols.iter().any(|symbol| {
+                symbol.contains("::") && !symbol.starts_with("_ZN") && !symbol.starts_with("ZN")
+            }),
+            "expected a demangled `::`-separated symbol, got: {:?}",
+            symbols
+        );
+    }
+}
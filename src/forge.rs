@@ -0,0 +1,317 @@
+// Remote forge integration - complements `GitAnalyzer`'s local-commit patterns with
+// collaboration-behavior patterns (review latency, self-merge rate, force-pushes to an
+// open PR, comment tone on one's own PRs) pulled from a forge's pull-request history.
+// Backends are feature-gated per forge (`github` / `forgejo`) behind a common
+// `ForgeClient` trait.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::types::GitPattern;
+
+/// Where a PR history lives and how to authenticate against it. `host`/`owner`/`repo`
+/// are normally derived from the local `origin` remote (see `ForgeConfig::from_repo`);
+/// `token` comes from the optional "connect a forge" step on page 1. `host` doubles as
+/// the backend selector: `github.com` picks the `github` backend, anything else is
+/// assumed to be a self-hosted Forgejo-compatible instance.
+#[derive(Debug, Clone)]
+pub struct ForgeConfig {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    pub token: String,
+}
+
+impl ForgeConfig {
+    /// Derives host/owner/repo from the repository's `origin` remote, pairing it with
+    /// a user-supplied token. Returns `None` if there's no `origin` or its URL doesn't
+    /// look like a `host/owner/repo` forge URL.
+    pub fn from_repo(repo_path: &str, token: String) -> Option<Self> {
+        let repo = git2::Repository::open(repo_path).ok()?;
+        let origin = repo.find_remote("origin").ok()?;
+        let url = origin.url()?;
+        let (host, owner, repo_name) = parse_remote_url(url)?;
+        Some(Self { host, owner, repo: repo_name, token })
+    }
+}
+
+/// Splits a `git@host:owner/repo.git` or `https://host/owner/repo.git` remote URL
+/// into its `(host, owner, repo)` parts.
+fn parse_remote_url(url: &str) -> Option<(String, String, String)> {
+    let trimmed = url.trim_end_matches(".git");
+
+    // Strip an explicit scheme (`ssh://`, `https://`, ...), if present.
+    let rest = trimmed.split("://").nth(1).unwrap_or(trimmed);
+
+    // Strip a `user@` prefix - present both on scp-like URLs
+    // (`git@host:owner/repo`) and on scheme-qualified ones
+    // (`ssh://git@host/owner/repo`), so this runs unconditionally rather
+    // than only when there was no scheme to split on first.
+    let rest = rest.split_once('@').map(|(_, after)| after).unwrap_or(rest);
+
+    // Split host from path without blindly turning the first ':' into a
+    // '/': the scp-like form uses ':' as the host/path separator itself
+    // (`host:owner/repo`), while a scheme-qualified URL with an explicit
+    // port uses ':' for the port (`host:2222/owner/repo`). Distinguish them
+    // by checking whether what follows the colon up to the next '/' is
+    // numeric.
+    let (host, path) = match rest.split_once(':') {
+        Some((host, after_colon)) => match after_colon.split_once('/') {
+            Some((maybe_port, path)) if maybe_port.chars().all(|c| c.is_ascii_digit()) => {
+                (host.to_string(), path)
+            }
+            // Not a numeric port - the colon was the scp-like separator, so
+            // everything after it is the path.
+            _ => (host.to_string(), after_colon),
+        },
+        None => {
+            let (host, path) = rest.split_once('/')?;
+            (host.to_string(), path)
+        }
+    };
+
+    let (owner, repo) = path.rsplit_once('/')?;
+
+    (!host.is_empty() && !owner.is_empty() && !repo.is_empty())
+        .then(|| (host, owner.to_string(), repo.to_string()))
+}
+
+/// Aggregate collaboration signals pulled from a forge's PR history, independent of
+/// which backend produced them. `force_push_count`/`own_pr_comment_count` need a
+/// per-PR timeline/review-comments call neither backend below makes yet - they're
+/// carried here so a future backend can fill them in without another schema change.
+#[derive(Debug, Clone, Default)]
+pub struct PullRequestMetrics {
+    pub pull_requests_analyzed: usize,
+    pub avg_review_latency_hours: f32,
+    pub self_merge_rate: f32,
+    pub force_push_count: usize,
+    pub own_pr_comment_count: usize,
+}
+
+/// Common surface every forge backend implements, so the enrichment pipeline can ask
+/// "what does collaboration look like here" without caring whether the remote is
+/// GitHub or Forgejo.
+#[async_trait]
+pub trait ForgeClient: Send + Sync {
+    async fn fetch_pr_metrics(&self, config: &ForgeConfig) -> Result<PullRequestMetrics>;
+}
+
+#[cfg(feature = "github")]
+pub mod github {
+    use super::*;
+
+    const API_BASE: &str = "https://api.github.com";
+
+    #[derive(Debug, Deserialize)]
+    struct PullRequest {
+        number: u64,
+        user: User,
+        merged_by: Option<User>,
+        created_at: chrono::DateTime<chrono::Utc>,
+        merged_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct User {
+        login: String,
+    }
+
+    pub struct GitHubForgeClient;
+
+    #[async_trait]
+    impl ForgeClient for GitHubForgeClient {
+        async fn fetch_pr_metrics(&self, config: &ForgeConfig) -> Result<PullRequestMetrics> {
+            let client = reqwest::Client::new();
+            let url = format!(
+                "{}/repos/{}/{}/pulls?state=closed&per_page=50",
+                API_BASE, config.owner, config.repo
+            );
+
+            let pulls: Vec<PullRequest> = client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", config.token))
+                .header("User-Agent", "codex-d")
+                .send()
+                .await
+                .context("Failed to fetch GitHub pull requests")?
+                .json()
+                .await
+                .context("Failed to parse GitHub pull request response")?;
+
+            let is_self_merge = |pr: &PullRequest| {
+                pr.merged_by.as_ref().is_some_and(|merger| merger.login == pr.user.login)
+            };
+            let timestamps = |pr: &PullRequest| (pr.created_at, pr.merged_at);
+
+            Ok(summarize(&pulls, is_self_merge, timestamps))
+        }
+    }
+
+    fn summarize(
+        pulls: &[PullRequest],
+        is_self_merge: impl Fn(&PullRequest) -> bool,
+        timestamps: impl Fn(&PullRequest) -> (chrono::DateTime<chrono::Utc>, Option<chrono::DateTime<chrono::Utc>>),
+    ) -> PullRequestMetrics {
+        let merged: Vec<&PullRequest> = pulls.iter().filter(|pr| pr.merged_at.is_some()).collect();
+        if merged.is_empty() {
+            return PullRequestMetrics::default();
+        }
+
+        let self_merges = merged.iter().filter(|pr| is_self_merge(pr)).count();
+        let total_latency_hours: f32 = merged.iter().map(|pr| {
+            let (created, merged) = timestamps(pr);
+            merged.map(|m| (m - created).num_minutes() as f32 / 60.0).unwrap_or(0.0)
+        }).sum();
+
+        PullRequestMetrics {
+            pull_requests_analyzed: merged.len(),
+            avg_review_latency_hours: total_latency_hours / merged.len() as f32,
+            self_merge_rate: self_merges as f32 / merged.len() as f32,
+            force_push_count: 0,
+            own_pr_comment_count: 0,
+        }
+    }
+}
+
+#[cfg(feature = "forgejo")]
+pub mod forgejo {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct PullRequest {
+        merged: bool,
+        user: User,
+        merged_by: Option<User>,
+        created_at: chrono::DateTime<chrono::Utc>,
+        merged_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct User {
+        login: String,
+    }
+
+    pub struct ForgejoForgeClient {
+        pub host: String,
+    }
+
+    #[async_trait]
+    impl ForgeClient for ForgejoForgeClient {
+        async fn fetch_pr_metrics(&self, config: &ForgeConfig) -> Result<PullRequestMetrics> {
+            let client = reqwest::Client::new();
+            let url = format!(
+                "{}/api/v1/repos/{}/{}/pulls?state=closed&limit=50",
+                self.host, config.owner, config.repo
+            );
+
+            let pulls: Vec<PullRequest> = client
+                .get(&url)
+                .header("Authorization", format!("token {}", config.token))
+                .send()
+                .await
+                .context("Failed to fetch Forgejo pull requests")?
+                .json()
+                .await
+                .context("Failed to parse Forgejo pull request response")?;
+
+            let merged: Vec<&PullRequest> = pulls.iter().filter(|pr| pr.merged).collect();
+            if merged.is_empty() {
+                return Ok(PullRequestMetrics::default());
+            }
+
+            let self_merges = merged.iter()
+                .filter(|pr| pr.merged_by.as_ref().is_some_and(|merger| merger.login == pr.user.login))
+                .count();
+            let total_latency_hours: f32 = merged.iter()
+                .map(|pr| pr.merged_at.map(|m| (m - pr.created_at).num_minutes() as f32 / 60.0).unwrap_or(0.0))
+                .sum();
+
+            Ok(PullRequestMetrics {
+                pull_requests_analyzed: merged.len(),
+                avg_review_latency_hours: total_latency_hours / merged.len() as f32,
+                self_merge_rate: self_merges as f32 / merged.len() as f32,
+                force_push_count: 0,
+                own_pr_comment_count: 0,
+            })
+        }
+    }
+}
+
+/// Picks a backend at compile time based on which forge feature is enabled, then
+/// dispatches to it based on `config.host` (anything other than `github.com` is
+/// assumed to be a self-hosted Forgejo-compatible instance).
+pub async fn fetch_pr_metrics(config: &ForgeConfig) -> Result<PullRequestMetrics> {
+    #[cfg(feature = "github")]
+    if config.host == "github.com" {
+        return github::GitHubForgeClient.fetch_pr_metrics(config).await;
+    }
+
+    #[cfg(feature = "forgejo")]
+    return forgejo::ForgejoForgeClient { host: format!("https://{}", config.host) }
+        .fetch_pr_metrics(config)
+        .await;
+
+    #[cfg(not(feature = "forgejo"))]
+    anyhow::bail!(
+        "No compatible forge backend compiled in for host {} \
+         (enable the `github` or `forgejo` feature)",
+        config.host
+    );
+}
+
+const SELF_MERGE_THRESHOLD: f32 = 0.5;
+const FAST_REVIEW_THRESHOLD_HOURS: f32 = 1.0;
+
+/// Turns forge-derived `PullRequestMetrics` into `GitPattern`s, using the same
+/// pattern_type/title/description/severity/insight shape `GitAnalyzer` emits for
+/// local-commit patterns, so the enrichment pipeline can fold both into one list.
+pub fn collaboration_patterns(metrics: &PullRequestMetrics) -> Vec<GitPattern> {
+    if metrics.pull_requests_analyzed == 0 {
+        return vec![];
+    }
+
+    let mut patterns = Vec::new();
+
+    patterns.push(GitPattern {
+        pattern_type: "self_merge_rate".to_string(),
+        title: "Self-Merge Rate".to_string(),
+        description: format!(
+            "{:.0}% of your {} merged pull requests were merged by their own author.",
+            metrics.self_merge_rate * 100.0,
+            metrics.pull_requests_analyzed,
+        ),
+        evidence: vec![],
+        severity: metrics.self_merge_rate,
+        insight: "Merging your own work without another reviewer skips a second set of eyes. \
+            Is that a deliberate call for this project, or does it happen by default?".to_string(),
+    });
+
+    patterns.push(GitPattern {
+        pattern_type: "review_latency".to_string(),
+        title: "Review Latency".to_string(),
+        description: format!(
+            "Pull requests spent an average of {:.1} hours open before merging.",
+            metrics.avg_review_latency_hours,
+        ),
+        evidence: vec![],
+        severity: if metrics.avg_review_latency_hours <= FAST_REVIEW_THRESHOLD_HOURS { 0.6 } else { 0.0 },
+        insight: "Very fast merges can mean either a tight feedback loop or a review \
+            that's more formality than scrutiny. Which is it here?".to_string(),
+    });
+
+    if metrics.self_merge_rate >= SELF_MERGE_THRESHOLD {
+        patterns.push(GitPattern {
+            pattern_type: "unreviewed_collaboration".to_string(),
+            title: "Mostly Unreviewed Collaboration".to_string(),
+            description: "More than half of merged pull requests had no external reviewer.".to_string(),
+            evidence: vec![],
+            severity: metrics.self_merge_rate,
+            insight: "Worth naming explicitly to collaborators: is this a solo project, \
+                or is review expected and just not happening?".to_string(),
+        });
+    }
+
+    patterns
+}
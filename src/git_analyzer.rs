@@ -1,45 +1,191 @@
 // Git repository analysis - detects behavioral patterns in commit history
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use git2::{DiffOptions, Repository, Time};
+use rayon::prelude::*;
 use std::collections::HashSet;
 use std::path::Path;
 use chrono::{DateTime, Datelike, Timelike, Utc};
 
-use crate::types::{CommitEvidence, GitAnalysis, GitPattern};
+use crate::types::{
+    AuthorHours, CommitEvidence, GitAnalysis, GitPattern, GitWarning, GitWarningKind,
+    TimeInvestment,
+};
 
 pub struct GitAnalyzer;
 
+/// Tunable parameters for `GitAnalyzer::analyze`.
+#[derive(Debug, Clone)]
+pub struct AnalyzeOptions {
+    /// Gaps between an author's consecutive commits below this threshold (in minutes)
+    /// are counted as continuous work; larger gaps start a new session.
+    pub max_commit_diff_minutes: f32,
+    /// Minutes credited for the first commit of a session (git-hours heuristic).
+    pub first_commit_addition_minutes: f32,
+    /// Narrows which commits are collected and scored.
+    pub filter: CommitFilter,
+}
+
+impl Default for AnalyzeOptions {
+    fn default() -> Self {
+        Self {
+            max_commit_diff_minutes: 120.0,
+            first_commit_addition_minutes: 120.0,
+            filter: CommitFilter::default(),
+        }
+    }
+}
+
+/// Narrows which commits `GitAnalyzer::analyze` collects and scores, so behavioral
+/// analysis can be scoped to a subsystem or a single contributor instead of always
+/// running over the last 100 HEAD commits.
+#[derive(Debug, Clone, Default)]
+pub struct CommitFilter {
+    /// Free-text substring matched against the commit message (case-insensitive).
+    pub message_contains: Option<String>,
+    /// A simple glob (supports a single `*` wildcard) matched against touched file paths.
+    pub path_glob: Option<String>,
+    /// Exact author name match (case-insensitive).
+    pub author: Option<String>,
+    /// Unix timestamp lower bound (inclusive).
+    pub since: Option<i64>,
+    /// Unix timestamp upper bound (inclusive).
+    pub until: Option<i64>,
+}
+
+impl CommitFilter {
+    pub fn is_empty(&self) -> bool {
+        self.message_contains.is_none()
+            && self.path_glob.is_none()
+            && self.author.is_none()
+            && self.since.is_none()
+            && self.until.is_none()
+    }
+
+    fn matches(&self, message: &str, author: &str, timestamp: i64, files_changed: &[String]) -> bool {
+        if let Some(query) = &self.message_contains {
+            if !message.to_lowercase().contains(&query.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if let Some(expected_author) = &self.author {
+            if !author.eq_ignore_ascii_case(expected_author) {
+                return false;
+            }
+        }
+
+        if let Some(since) = self.since {
+            if timestamp < since {
+                return false;
+            }
+        }
+
+        if let Some(until) = self.until {
+            if timestamp > until {
+                return false;
+            }
+        }
+
+        if let Some(glob) = &self.path_glob {
+            if !files_changed.iter().any(|path| Self::glob_match(glob, path)) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Minimal glob matching: a single `*` splits the pattern into a prefix and
+    /// suffix that must both appear, in order, within the path.
+    fn glob_match(glob: &str, path: &str) -> bool {
+        match glob.split_once('*') {
+            Some((prefix, suffix)) => {
+                path.starts_with(prefix) && path.ends_with(suffix) && path.len() >= prefix.len() + suffix.len()
+            }
+            None => path.starts_with(glob),
+        }
+    }
+
+    fn describe(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut parts = Vec::new();
+        if let Some(q) = &self.message_contains {
+            parts.push(format!("message contains \"{}\"", q));
+        }
+        if let Some(g) = &self.path_glob {
+            parts.push(format!("path matches \"{}\"", g));
+        }
+        if let Some(a) = &self.author {
+            parts.push(format!("author is \"{}\"", a));
+        }
+        if self.since.is_some() || self.until.is_some() {
+            parts.push("within the given date range".to_string());
+        }
+
+        Some(parts.join(", "))
+    }
+}
+
 #[derive(Debug)]
 struct CommitData {
     sha: String,
     message: String,
+    author: String,
     lines_changed: usize,
     timestamp: i64,
     hour: u32,
     day_of_week: u32,
     files_changed: Vec<String>,
+    conventional: Option<ConventionalCommit>,
+}
+
+/// A commit message parsed as a Conventional Commit header: `type(scope)!: description`.
+#[derive(Debug, Clone)]
+struct ConventionalCommit {
+    commit_type: String,
+    scope: Option<String>,
+    breaking: bool,
 }
 
+const CONVENTIONAL_COMMIT_TYPES: &[&str] = &[
+    "feat", "fix", "refactor", "chore", "docs", "style", "test", "perf", "build", "ci", "revert",
+];
+
 impl GitAnalyzer {
-    /// Analyze a git repository for behavioral patterns
+    /// Analyze a git repository for behavioral patterns, using default options.
     pub async fn analyze<F>(repo_path: impl AsRef<Path>, progress_callback: F) -> Result<GitAnalysis>
     where
-        F: Fn(String, f32) + Send + 'static,
+        F: Fn(String, f32) + Send + Sync + 'static,
+    {
+        Self::analyze_with_options(repo_path, progress_callback, AnalyzeOptions::default()).await
+    }
+
+    /// Analyze a git repository for behavioral patterns with tunable options.
+    pub async fn analyze_with_options<F>(
+        repo_path: impl AsRef<Path>,
+        progress_callback: F,
+        options: AnalyzeOptions,
+    ) -> Result<GitAnalysis>
+    where
+        F: Fn(String, f32) + Send + Sync + 'static,
     {
         let repo_path = repo_path.as_ref().to_path_buf();
 
         // Run blocking git operations in background thread
         tokio::task::spawn_blocking(move || {
-            Self::analyze_blocking(&repo_path, progress_callback)
+            Self::analyze_blocking(&repo_path, progress_callback, &options)
         })
         .await
         .context("Git analyzer task failed")?
     }
 
-    fn analyze_blocking<F>(repo_path: &Path, progress_cb: F) -> Result<GitAnalysis>
+    fn analyze_blocking<F>(repo_path: &Path, progress_cb: F, options: &AnalyzeOptions) -> Result<GitAnalysis>
     where
-        F: Fn(String, f32),
+        F: Fn(String, f32) + Send + Sync,
     {
         progress_cb("Opening repository".to_string(), 5.0);
         let repo = Repository::open(repo_path)
@@ -47,7 +193,7 @@ impl GitAnalyzer {
 
         // Collect commit data
         progress_cb("Collecting commits".to_string(), 15.0);
-        let commits = Self::collect_commits(&repo, 100)?;
+        let commits = Self::collect_commits(repo_path, &repo, 100, &options.filter, &progress_cb)?;
 
         if commits.is_empty() {
             progress_cb("Analysis complete".to_string(), 100.0);
@@ -56,6 +202,12 @@ impl GitAnalyzer {
                 summary: "No commits found in repository".to_string(),
                 total_commits_analyzed: 0,
                 severity: 0.0,
+                time_investment: TimeInvestment {
+                    total_hours: 0.0,
+                    by_author: vec![],
+                },
+                commit_activity: vec![],
+                warnings: vec![],
             });
         }
 
@@ -64,6 +216,11 @@ impl GitAnalyzer {
         // Detect all patterns
         let mut patterns = Vec::new();
 
+        // 0. Time Investment (git-hours heuristic)
+        progress_cb("Estimating time investment".to_string(), 30.0);
+        let time_investment = Self::estimate_time_investment(&commits, options);
+        patterns.push(Self::time_investment_pattern(&time_investment));
+
         // 1. Minimizing Language Pattern
         progress_cb("Detecting minimizing language".to_string(), 35.0);
         if let Some(pattern) = Self::detect_minimizing_language(&commits) {
@@ -94,6 +251,22 @@ impl GitAnalyzer {
             patterns.push(pattern);
         }
 
+        // 6. Conventional Commit Drift
+        progress_cb("Analyzing conventional commit types".to_string(), 90.0);
+        if let Some(pattern) = Self::detect_conventional_commit_patterns(&commits) {
+            patterns.push(pattern);
+        }
+
+        // 7. Ownership Concentration / Bus Factor
+        progress_cb("Analyzing code ownership".to_string(), 92.0);
+        if let Some(pattern) = Self::detect_ownership_concentration(&repo)? {
+            patterns.push(pattern);
+        }
+
+        // Structured warnings (terse, unscored triage flags) are separate from patterns.
+        progress_cb("Scanning for risky repo signals".to_string(), 94.0);
+        let warnings = Self::detect_warnings(&commits);
+
         // Calculate overall severity
         progress_cb("Calculating severity".to_string(), 95.0);
         let severity = if patterns.is_empty() {
@@ -103,29 +276,62 @@ impl GitAnalyzer {
         };
 
         // Generate summary
-        let summary = Self::generate_summary(&patterns, commits.len());
+        let summary = Self::generate_summary(&patterns, commits.len(), &options.filter);
 
         progress_cb("Analysis complete".to_string(), 100.0);
 
+        let commit_activity = commits.iter()
+            .map(|c| crate::types::CommitActivity { timestamp: c.timestamp })
+            .collect();
+
         Ok(GitAnalysis {
             patterns,
             summary,
             total_commits_analyzed: commits.len(),
             severity,
+            time_investment,
+            commit_activity,
+            warnings,
         })
     }
 
-    fn collect_commits(repo: &Repository, limit: usize) -> Result<Vec<CommitData>> {
+    /// Collects commit metadata sequentially (cheap, revwalk order is meaningful),
+    /// then fans the expensive tree-to-tree diffing out across a rayon thread pool,
+    /// since that's what dominates runtime on large histories. Each worker opens its
+    /// own `Repository` handle because `git2` objects aren't `Send`.
+    fn collect_commits<F>(
+        repo_path: &Path,
+        repo: &Repository,
+        limit: usize,
+        filter: &CommitFilter,
+        progress_cb: &F,
+    ) -> Result<Vec<CommitData>>
+    where
+        F: Fn(String, f32) + Send + Sync,
+    {
         let mut revwalk = repo.revwalk()
             .context("Failed to create revwalk")?;
 
         revwalk.push_head()
             .context("Failed to push HEAD")?;
 
-        let mut commits = Vec::new();
+        struct CommitMeta {
+            id: git2::Oid,
+            sha: String,
+            message: String,
+            author: String,
+            timestamp: i64,
+            hour: u32,
+            day_of_week: u32,
+            conventional: Option<ConventionalCommit>,
+            files_changed: Vec<String>,
+        }
+
+        let mut metas = Vec::new();
+        let mut matched = 0;
 
-        for (idx, commit_id) in revwalk.enumerate() {
-            if idx >= limit {
+        for commit_id in revwalk {
+            if matched >= limit {
                 break;
             }
 
@@ -134,24 +340,84 @@ impl GitAnalyzer {
                 .context("Failed to find commit")?;
 
             let message = commit.summary().unwrap_or("").to_string();
-            let lines_changed = Self::get_commit_stats(&repo, &commit)?;
+            let full_message = commit.message().unwrap_or("").to_string();
+            let author = commit.author().name().unwrap_or("unknown").to_string();
             let time = commit.time();
-
-            // Convert to DateTime for analysis
             let dt = Self::git_time_to_datetime(&time);
 
-            // Get files changed
+            // Checking the filter needs the changed-file list, which already requires a
+            // diff — but `get_changed_files` (no stats) is far cheaper than the full
+            // tree-to-tree diff with insertion/deletion counts done below, so we pay for
+            // it here and skip non-matching commits before the expensive parallel pass.
             let files_changed = Self::get_changed_files(&repo, &commit)?;
 
-            commits.push(CommitData {
+            if !filter.matches(&message, &author, time.seconds(), &files_changed) {
+                continue;
+            }
+
+            let conventional = Self::parse_conventional_commit(&message, &full_message);
+
+            metas.push(CommitMeta {
+                id: commit_id,
                 sha: commit_id.to_string()[..7].to_string(),
                 message,
-                lines_changed,
+                author,
                 timestamp: time.seconds(),
                 hour: dt.hour(),
                 day_of_week: dt.weekday().num_days_from_monday(),
+                conventional,
                 files_changed,
             });
+            matched += 1;
+        }
+
+        // Diffing is the expensive part (a full tree-to-tree diff per commit), so fan
+        // it out across a thread pool. A shared atomic counter drives the progress
+        // callback smoothly as stats complete, instead of the fixed checkpoints used
+        // for the cheaper metadata pass above.
+        let completed = std::sync::atomic::AtomicUsize::new(0);
+        let total = metas.len();
+
+        let diff_results: Vec<Result<(git2::Oid, usize)>> = metas
+            .par_iter()
+            .map(|meta| {
+                let repo = Repository::open(repo_path).context("Failed to open git repository")?;
+                let commit = repo.find_commit(meta.id).context("Failed to find commit")?;
+
+                let lines_changed = Self::get_commit_stats(&repo, &commit)?;
+
+                let done = completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+                let progress = 15.0 + (done as f32 / total.max(1) as f32) * 10.0;
+                progress_cb(format!("Diffing commits ({done}/{total})"), progress);
+
+                Ok((meta.id, lines_changed))
+            })
+            .collect();
+
+        let mut lines_by_id: std::collections::HashMap<git2::Oid, usize> =
+            std::collections::HashMap::with_capacity(metas.len());
+        for result in diff_results {
+            let (id, lines_changed) = result?;
+            lines_by_id.insert(id, lines_changed);
+        }
+
+        // Re-assemble in the original revwalk order — rayon gives us no ordering guarantee.
+        let mut commits = Vec::with_capacity(metas.len());
+        for meta in metas {
+            let lines_changed = lines_by_id.remove(&meta.id)
+                .ok_or_else(|| anyhow!("Missing diff stats for commit {}", meta.sha))?;
+
+            commits.push(CommitData {
+                sha: meta.sha,
+                message: meta.message,
+                author: meta.author,
+                lines_changed,
+                timestamp: meta.timestamp,
+                hour: meta.hour,
+                day_of_week: meta.day_of_week,
+                files_changed: meta.files_changed,
+                conventional: meta.conventional,
+            });
         }
 
         Ok(commits)
@@ -380,6 +646,108 @@ impl GitAnalyzer {
         })
     }
 
+    /// Parse a Conventional Commits header (`type(scope)!: description`) from the
+    /// commit summary, falling back to scanning the full message for a
+    /// `BREAKING CHANGE:` footer.
+    fn parse_conventional_commit(summary: &str, full_message: &str) -> Option<ConventionalCommit> {
+        let colon_idx = summary.find(':')?;
+        let header = summary[..colon_idx].trim();
+
+        let (header, bang_breaking) = match header.strip_suffix('!') {
+            Some(stripped) => (stripped, true),
+            None => (header, false),
+        };
+
+        let (commit_type, scope) = match (header.find('('), header.find(')')) {
+            (Some(open), Some(close)) if close > open => (
+                header[..open].to_string(),
+                Some(header[open + 1..close].to_string()),
+            ),
+            _ => (header.to_string(), None),
+        };
+
+        if !CONVENTIONAL_COMMIT_TYPES.contains(&commit_type.as_str()) {
+            return None;
+        }
+
+        let footer_breaking = full_message.contains("BREAKING CHANGE:");
+
+        Some(ConventionalCommit {
+            commit_type,
+            scope,
+            breaking: bang_breaking || footer_breaking,
+        })
+    }
+
+    /// Reports conventional-commit type distribution, the unconventional-commit ratio,
+    /// and flags cases where the declared type doesn't match the actual diff shape
+    /// (e.g. a `feat:` that's really a rewrite, or a `chore:` touching hundreds of lines).
+    fn detect_conventional_commit_patterns(commits: &[CommitData]) -> Option<GitPattern> {
+        if commits.is_empty() {
+            return None;
+        }
+
+        let typed_count = commits.iter().filter(|c| c.conventional.is_some()).count();
+        let untyped_ratio = 1.0 - (typed_count as f32 / commits.len() as f32);
+
+        let mut type_counts: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+        for commit in commits {
+            if let Some(conv) = &commit.conventional {
+                *type_counts.entry(conv.commit_type.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        let mut evidence = Vec::new();
+        for commit in commits {
+            let Some(conv) = &commit.conventional else { continue };
+
+            let mismatched = match conv.commit_type.as_str() {
+                "feat" | "fix" => commit.lines_changed > 300,
+                "chore" | "docs" | "style" => commit.lines_changed > 200,
+                _ => false,
+            };
+
+            if mismatched {
+                evidence.push(CommitEvidence {
+                    sha: commit.sha.clone(),
+                    message: commit.message.clone(),
+                    lines_changed: commit.lines_changed,
+                });
+            }
+        }
+
+        // Nothing meaningfully off if the repo mostly uses conventional commits
+        // cleanly and few are mismatched.
+        if untyped_ratio < 0.5 && evidence.is_empty() {
+            return None;
+        }
+
+        let type_distribution = type_counts.iter()
+            .map(|(t, n)| format!("{}: {}", t, n))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let severity = (untyped_ratio * 0.5 + (evidence.len() as f32 / commits.len() as f32) * 0.5).min(1.0);
+
+        Some(GitPattern {
+            pattern_type: "conventional_commit_drift".to_string(),
+            title: "Conventional Commit Type Drift".to_string(),
+            description: format!(
+                "{}% of commits don't follow Conventional Commits formatting. Type distribution: {}. \
+                {} commit(s) have a declared type that doesn't match the actual scope of the diff \
+                (e.g. 'feat'/'fix' with a much larger change than the label suggests).",
+                (untyped_ratio * 100.0) as u32,
+                if type_distribution.is_empty() { "none parsed".to_string() } else { type_distribution },
+                evidence.len(),
+            ),
+            evidence,
+            severity,
+            insight: "When the declared commit type doesn't match the real scope of a change, \
+                reviewers and future-you lose a reliable signal for what actually happened. \
+                What would it take to label changes by what they really are?".to_string(),
+        })
+    }
+
     fn detect_file_avoidance(commits: &[CommitData], repo: &Repository) -> Result<Option<GitPattern>> {
         // Get all files in repo
         let head = repo.head()?;
@@ -438,6 +806,250 @@ impl GitAnalyzer {
         }))
     }
 
+    /// Attributes each tracked file's current lines to their last-modifying author via
+    /// `git2` blame, then reports ownership concentration: files where one author owns
+    /// the overwhelming majority of lines (knowledge silos / bus-factor risk). Complements
+    /// `detect_file_avoidance` by distinguishing "avoided" files from "owned by one person".
+    fn detect_ownership_concentration(repo: &Repository) -> Result<Option<GitPattern>> {
+        const OWNERSHIP_THRESHOLD: f32 = 0.8;
+
+        let head = repo.head()?;
+        let tree = head.peel_to_tree()?;
+
+        let mut all_paths = Vec::new();
+        tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() == Some(git2::ObjectType::Blob) {
+                if let Some(name) = entry.name() {
+                    all_paths.push(format!("{}{}", dir, name));
+                }
+            }
+            0
+        })?;
+
+        let mut concentrated_files = Vec::new();
+        let mut blamed_file_count = 0;
+
+        for path in &all_paths {
+            let blame = match repo.blame_file(Path::new(path), None) {
+                Ok(blame) => blame,
+                Err(_) => continue, // binary/unreadable files, etc.
+            };
+
+            let mut lines_by_author: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+            let mut total_lines = 0usize;
+
+            for hunk in blame.iter() {
+                let author = hunk.final_signature().name().unwrap_or("unknown").to_string();
+                let lines = hunk.lines_in_hunk();
+                *lines_by_author.entry(author).or_insert(0) += lines;
+                total_lines += lines;
+            }
+
+            if total_lines == 0 {
+                continue;
+            }
+            blamed_file_count += 1;
+
+            if let Some((dominant_author, owned_lines)) = lines_by_author.into_iter().max_by_key(|(_, lines)| *lines) {
+                let ownership_pct = owned_lines as f32 / total_lines as f32;
+                if ownership_pct >= OWNERSHIP_THRESHOLD {
+                    concentrated_files.push((path.clone(), dominant_author, ownership_pct));
+                }
+            }
+        }
+
+        if concentrated_files.is_empty() || blamed_file_count == 0 {
+            return Ok(None);
+        }
+
+        // Worst silos first.
+        concentrated_files.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+        let concentration_ratio = concentrated_files.len() as f32 / blamed_file_count as f32;
+
+        let examples = concentrated_files.iter()
+            .take(5)
+            .map(|(path, author, pct)| format!("{} ({} owns {}%)", path, author, (pct * 100.0) as u32))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Ok(Some(GitPattern {
+            pattern_type: "bus_factor".to_string(),
+            title: "Ownership Concentration / Bus Factor".to_string(),
+            description: format!(
+                "{}% of tracked files ({}/{}) have one author owning {}%+ of their current lines. \
+                Examples: {}. This is a knowledge-silo risk — if the dominant author leaves, \
+                these files become much harder to maintain.",
+                (concentration_ratio * 100.0) as u32,
+                concentrated_files.len(),
+                blamed_file_count,
+                (OWNERSHIP_THRESHOLD * 100.0) as u32,
+                examples,
+            ),
+            evidence: vec![],
+            severity: concentration_ratio.min(0.8),
+            insight: "Single-owner files are efficient day-to-day but risky long-term. \
+                Would pairing or review rotation on these files reduce the bus-factor risk?".to_string(),
+        }))
+    }
+
+    /// Scans for terse, discrete risk signals that don't warrant a full scored `GitPattern`:
+    /// a commit that reverts another within minutes, a burst of commits sharing an identical
+    /// message, a commit touching an unusually large number of files, or a long stretch with
+    /// no commits at all. Unlike patterns, warnings are unscored — just listed for triage.
+    fn detect_warnings(commits: &[CommitData]) -> Vec<GitWarning> {
+        const QUICK_REVERT_MINUTES: i64 = 15;
+        const DUPLICATE_MESSAGE_BURST_SIZE: usize = 3;
+        const MASSIVE_FILE_TOUCH_COUNT: usize = 50;
+        const LONG_COMMIT_GAP_DAYS: i64 = 30;
+
+        let mut warnings = Vec::new();
+
+        // Detectors below reason about elapsed time between commits, so work in
+        // chronological (oldest-first) order rather than the revwalk's newest-first order.
+        let mut chronological: Vec<&CommitData> = commits.iter().collect();
+        chronological.sort_by_key(|c| c.timestamp);
+
+        for window in chronological.windows(2) {
+            let (prev, curr) = (window[0], window[1]);
+            let gap_minutes = (curr.timestamp - prev.timestamp) as f64 / 60.0;
+
+            if curr.message.to_lowercase().contains("revert") && gap_minutes <= QUICK_REVERT_MINUTES as f64 {
+                warnings.push(GitWarning {
+                    kind: GitWarningKind::QuickRevert,
+                    message: format!(
+                        "\"{}\" reverts work from {} minutes earlier ({})",
+                        curr.message.lines().next().unwrap_or(&curr.message),
+                        gap_minutes.round() as i64,
+                        prev.message.lines().next().unwrap_or(&prev.message),
+                    ),
+                    commit_shas: vec![prev.sha.clone(), curr.sha.clone()],
+                });
+            }
+
+            let gap_days = (curr.timestamp - prev.timestamp) / 86_400;
+            if gap_days >= LONG_COMMIT_GAP_DAYS {
+                warnings.push(GitWarning {
+                    kind: GitWarningKind::LongCommitGap,
+                    message: format!(
+                        "{} days with no commits between \"{}\" and \"{}\"",
+                        gap_days,
+                        prev.message.lines().next().unwrap_or(&prev.message),
+                        curr.message.lines().next().unwrap_or(&curr.message),
+                    ),
+                    commit_shas: vec![prev.sha.clone(), curr.sha.clone()],
+                });
+            }
+        }
+
+        for commit in &chronological {
+            if commit.files_changed.len() > MASSIVE_FILE_TOUCH_COUNT {
+                warnings.push(GitWarning {
+                    kind: GitWarningKind::MassiveFileTouch,
+                    message: format!(
+                        "\"{}\" touches {} files in one commit",
+                        commit.message.lines().next().unwrap_or(&commit.message),
+                        commit.files_changed.len(),
+                    ),
+                    commit_shas: vec![commit.sha.clone()],
+                });
+            }
+        }
+
+        let mut run_start = 0usize;
+        while run_start < chronological.len() {
+            let message = &chronological[run_start].message;
+            let mut run_end = run_start + 1;
+            while run_end < chronological.len() && &chronological[run_end].message == message {
+                run_end += 1;
+            }
+            let run = &chronological[run_start..run_end];
+            if run.len() >= DUPLICATE_MESSAGE_BURST_SIZE {
+                warnings.push(GitWarning {
+                    kind: GitWarningKind::DuplicateMessageBurst,
+                    message: format!(
+                        "{} consecutive commits share the message \"{}\"",
+                        run.len(),
+                        message.lines().next().unwrap_or(message),
+                    ),
+                    commit_shas: run.iter().map(|c| c.sha.clone()).collect(),
+                });
+            }
+            run_start = run_end;
+        }
+
+        warnings
+    }
+
+    /// Estimate hours invested per author using the standard "git-hours" heuristic:
+    /// consecutive commits within `max_commit_diff_minutes` are treated as one continuous
+    /// session (the exact gap is credited), while larger gaps start a new session credited
+    /// with a flat `first_commit_addition_minutes`.
+    fn estimate_time_investment(commits: &[CommitData], options: &AnalyzeOptions) -> TimeInvestment {
+        use std::collections::BTreeMap;
+
+        let mut by_author: BTreeMap<&str, Vec<i64>> = BTreeMap::new();
+        for commit in commits {
+            by_author.entry(commit.author.as_str()).or_default().push(commit.timestamp);
+        }
+
+        let max_diff_secs = (options.max_commit_diff_minutes * 60.0) as i64;
+        let first_commit_addition_secs = (options.first_commit_addition_minutes * 60.0) as i64;
+
+        let mut author_hours = Vec::new();
+        let mut total_seconds: i64 = 0;
+
+        for (author, mut timestamps) in by_author {
+            timestamps.sort_unstable();
+
+            let mut author_seconds: i64 = first_commit_addition_secs;
+            for window in timestamps.windows(2) {
+                let gap = window[1] - window[0];
+                author_seconds += if gap < max_diff_secs {
+                    gap
+                } else {
+                    first_commit_addition_secs
+                };
+            }
+
+            total_seconds += author_seconds;
+            author_hours.push(AuthorHours {
+                author: author.to_string(),
+                hours: author_seconds as f32 / 3600.0,
+            });
+        }
+
+        // Whoever carries the most hours first.
+        author_hours.sort_by(|a, b| b.hours.partial_cmp(&a.hours).unwrap_or(std::cmp::Ordering::Equal));
+
+        TimeInvestment {
+            total_hours: total_seconds as f32 / 3600.0,
+            by_author: author_hours,
+        }
+    }
+
+    fn time_investment_pattern(time_investment: &TimeInvestment) -> GitPattern {
+        let top_contributor = time_investment.by_author.first()
+            .map(|a| format!("{} ({:.1}h)", a.author, a.hours))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        GitPattern {
+            pattern_type: "time_investment".to_string(),
+            title: "Estimated Time Investment".to_string(),
+            description: format!(
+                "Estimated {:.1} hours invested across {} contributor(s), using commit session gaps. \
+                Top contributor: {}.",
+                time_investment.total_hours,
+                time_investment.by_author.len(),
+                top_contributor,
+            ),
+            evidence: vec![],
+            severity: 0.0,
+            insight: "This is a rough estimate from commit timing, not actual hours worked. \
+                Does it roughly match your own sense of how much time went into this?".to_string(),
+        }
+    }
+
     fn get_commit_stats(repo: &Repository, commit: &git2::Commit) -> Result<usize> {
         let current_tree = commit.tree()
             .context("Failed to get commit tree")?;
@@ -495,9 +1107,16 @@ impl GitAnalyzer {
         DateTime::from_timestamp(time.seconds(), 0).unwrap_or_else(|| Utc::now())
     }
 
-    fn generate_summary(patterns: &[GitPattern], total_commits: usize) -> String {
+    fn generate_summary(patterns: &[GitPattern], total_commits: usize, filter: &CommitFilter) -> String {
+        let filter_note = filter.describe()
+            .map(|desc| format!(" (filtered: {})", desc))
+            .unwrap_or_default();
+
         if patterns.is_empty() {
-            return format!("Analyzed {} commits. No significant behavioral patterns detected.", total_commits);
+            return format!(
+                "Analyzed {} commits{}. No significant behavioral patterns detected.",
+                total_commits, filter_note,
+            );
         }
 
         let pattern_titles: Vec<&str> = patterns.iter()
@@ -505,8 +1124,9 @@ impl GitAnalyzer {
             .collect();
 
         format!(
-            "Analyzed {} commits and detected {} behavioral patterns: {}",
+            "Analyzed {} commits{} and detected {} behavioral patterns: {}",
             total_commits,
+            filter_note,
             patterns.len(),
             pattern_titles.join(", ")
         )
@@ -535,4 +1155,33 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn collect_commits_preserves_revwalk_order_despite_parallel_diffing() {
+        // Rayon's `par_iter` makes no guarantee about which commit finishes its
+        // diff first - this test guards the re-assembly step that puts the
+        // per-commit stats back in revwalk (i.e. newest-first) order.
+        let Ok(repo) = Repository::open(".") else {
+            // No git repo available in this environment - nothing to assert.
+            return;
+        };
+
+        let options = AnalyzeOptions::default();
+        let commits = GitAnalyzer::collect_commits(
+            Path::new("."),
+            &repo,
+            5,
+            &options.filter,
+            &|_msg: String, _pct: f32| {},
+        )
+        .expect("collect_commits should succeed against this repo");
+
+        assert!(!commits.is_empty());
+        for pair in commits.windows(2) {
+            assert!(
+                pair[0].timestamp >= pair[1].timestamp,
+                "commits should stay in descending-timestamp revwalk order"
+            );
+        }
+    }
 }
@@ -2,15 +2,29 @@
 // Chat-based UI: Repo selection → Enrichment → Conversation
 
 mod types;
+mod audit_store;
+mod cargo_audit;
+mod controller;
+mod crash_capture;
+mod forge;
 mod git_analyzer;
+mod git_repository;
+mod client_capabilities;
+mod transport;
 mod codex_adapter;
+mod project_context;
+mod repo_discovery;
+mod semantic_index;
 mod storage;
+#[cfg(feature = "tui")]
+mod tui;
 mod ui;
+mod webhook;
+mod workload;
 
 use gpui::*;
 use gpui::prelude::*;
 use std::sync::Arc;
-use std::collections::HashMap;
 use tokio::runtime::Handle;
 
 use gpui_component::{
@@ -19,12 +33,16 @@ use gpui_component::{
     Root,
 };
 
-use types::{AppState, Message, TimelineEvent};
+use controller::CodexController;
+use types::{now_timestamp, AppState, Message, TimelineEvent};
 use storage::Storage;
-use git_analyzer::GitAnalyzer;
+use forge::ForgeConfig;
+use git_repository::{GitRepository, Libgit2GitRepository, SubprocessGitRepository};
+use webhook::WebhookDispatcher;
 use codex_adapter::CodexAdapter;
-use ui::timeline::render_timeline;
+use ui::virtualized_timeline::VirtualizedTimeline;
 use ui::components::{render_streaming_thought, render_streaming_message, render_streaming_tool_call};
+use ui::theme::Theme;
 
 fn main() {
     env_logger::init();
@@ -40,10 +58,55 @@ fn main() {
         });
     });
 
+    // `--tui` runs the same controller logic headlessly over a ratatui terminal
+    // UI instead of opening a GPUI window - useful over SSH or in headless CI.
+    #[cfg(feature = "tui")]
+    if std::env::args().any(|arg| arg == "--tui") {
+        let (storage, webhook) = init_shared_state(&tokio_handle);
+        let controller = CodexController::new(tokio_handle, storage, webhook);
+        if let Err(e) = tui::run(controller) {
+            eprintln!("TUI exited with error: {}", e);
+        }
+        return;
+    }
+
+    // `--bench <workload.json>` replays a recorded workload (see `workload::Workload`)
+    // back-to-back through the real audit-store recording path and prints a
+    // `BenchReport` - a headless way to catch regressions in timeline handling
+    // without opening a window.
+    if let Some(path) = std::env::args().skip_while(|arg| arg != "--bench").nth(1) {
+        let db_path = dirs::data_local_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("codex-d")
+            .join("bench.db");
+
+        let report = tokio_handle.block_on(async {
+            let store = audit_store::AuditStore::new(&db_path.to_string_lossy())?;
+            workload::bench_workload(
+                std::path::Path::new(&path),
+                move |event| {
+                    if let Err(err) = store.record("bench", event.clone()) {
+                        eprintln!("Failed to record bench event: {}", err);
+                    }
+                },
+            )
+        });
+
+        match report {
+            Ok(report) => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+            Err(err) => eprintln!("Bench run failed: {}", err),
+        }
+        return;
+    }
+
     Application::new().run(move |cx: &mut App| {
         // Initialize gpui-component
         gpui_component::init(cx);
 
+        // Bundled light theme by default; swap with `cx.set_global(Theme::dark())`
+        // to re-skin the whole timeline (see `ui::theme`).
+        cx.set_global(Theme::light());
+
         let bounds = Bounds::centered(None, size(px(900.0), px(700.0)), cx);
 
         cx.open_window(
@@ -60,72 +123,78 @@ fn main() {
     });
 }
 
+/// Builds the storage/webhook state shared by both frontends, identically to how
+/// `CodexView::new` sets them up for the GPUI window.
+fn init_shared_state(tokio_handle: &Handle) -> (Option<Arc<Storage>>, Option<Arc<WebhookDispatcher>>) {
+    let db_path = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("codex-d")
+        .join("messages.db");
+
+    let storage = Storage::new(&db_path.to_string_lossy())
+        .map(Arc::new)
+        .ok();
+
+    if storage.is_none() {
+        eprintln!("Failed to initialize storage");
+    }
+
+    let webhook = std::env::var("CODEXD_WEBHOOK_URL").ok().map(|endpoint| {
+        let secret = std::env::var("CODEXD_WEBHOOK_SECRET").unwrap_or_default();
+        let _guard = tokio_handle.enter();
+        Arc::new(WebhookDispatcher::new(
+            webhook::WebhookConfig { endpoint, secret },
+            |status| eprintln!("Webhook delivery [{}]: {:?}", status.tool_name, status.status),
+        ))
+    });
+
+    (storage, webhook)
+}
+
 struct CodexView {
-    app_state: AppState,
-    selected_repo: Option<String>,
+    controller: CodexController,
     repo_path_input: Entity<InputState>,
-    messages: Vec<Message>,
-    timeline_events: Vec<TimelineEvent>,
-    lifecycle_events: Vec<types::LifecycleEvent>,
     message_input_state: Entity<InputState>,
-    storage: Option<Arc<Storage>>,
-    tokio_handle: Handle,
-    codex_adapter: Option<Arc<CodexAdapter>>,
-    is_loading: bool,
-    error_message: Option<String>,
-    // Streaming state (temporary until event completes)
-    current_thought_buffer: String,
-    current_message_buffer: String,
-    active_tool_calls: HashMap<String, (types::ToolCallEvent, String)>, // (event, output)
-    // Enrichment timer
-    enrichment_start_time: Option<std::time::Instant>,
-    enrichment_elapsed: f32, // seconds
-    // Timeline scrolling
-    timeline_scroll_handle: ScrollHandle,
+    // Renders `controller.timeline_events` through a virtualized list so long
+    // sessions don't pay to materialize a `Div` per past event every frame;
+    // kept in sync at the top of `render_page_3`.
+    timeline_widget: Entity<VirtualizedTimeline>,
+    // Optional "connect a forge" step on page 1 - a personal access token for the
+    // repo's `origin` remote, used to pull PR collaboration patterns alongside the
+    // local commit patterns. `None` means enrichment stays local-only.
+    forge_token_input: Entity<InputState>,
 }
 
 impl CodexView {
     fn new(tokio_handle: Handle, window: &mut Window, cx: &mut Context<Self>) -> Self {
-        // Initialize storage
-        let db_path = dirs::data_local_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
-            .join("codex-d")
-            .join("messages.db");
-
-        let storage = Storage::new(&db_path.to_string_lossy())
-            .map(|s| Arc::new(s))
-            .ok();
-
-        if storage.is_none() {
-            eprintln!("Failed to initialize storage");
-        }
+        let (storage, webhook) = init_shared_state(&tokio_handle);
 
         // Create input states for text fields
         let repo_path_input = cx.new(|cx| InputState::new(window, cx));
         let message_input_state = cx.new(|cx| InputState::new(window, cx));
+        let forge_token_input = cx.new(|cx| InputState::new(window, cx));
 
         Self {
-            app_state: AppState::AwaitingRepoSelection,
-            selected_repo: None,
+            controller: CodexController::new(tokio_handle, storage, webhook),
             repo_path_input,
-            messages: Vec::new(),
-            timeline_events: Vec::new(),
-            lifecycle_events: Vec::new(),
             message_input_state,
-            storage,
-            tokio_handle,
-            codex_adapter: None,
-            is_loading: false,
-            error_message: None,
-            current_thought_buffer: String::new(),
-            current_message_buffer: String::new(),
-            active_tool_calls: HashMap::new(),
-            enrichment_start_time: None,
-            enrichment_elapsed: 0.0,
-            timeline_scroll_handle: ScrollHandle::new(),
+            timeline_widget: cx.new(|_cx| VirtualizedTimeline::new()),
+            forge_token_input,
         }
     }
 
+    fn on_connect_forge_clicked(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let token = self.forge_token_input.read(cx).text().to_string();
+        if token.trim().is_empty() {
+            return;
+        }
+        self.controller.forge_token = Some(token);
+        self.forge_token_input.update(cx, |state, cx| {
+            state.set_value("", window, cx);
+        });
+        cx.notify();
+    }
+
     fn on_browse_clicked(&mut self, cx: &mut Context<Self>) {
         // TODO: File picker implementation - complex async pattern
         // For now, user can type path directly in input field
@@ -134,112 +203,275 @@ impl CodexView {
         let path_str = self.repo_path_input.read(cx).text().to_string();
 
         if path_str.trim().is_empty() {
-            self.error_message = Some("Please enter a repository path".to_string());
+            self.controller.error_message = Some("Please enter a repository path".to_string());
             cx.notify();
             return;
         }
 
+        self.on_path_selected(path_str, cx);
+    }
+
+    /// Handles a user-supplied path from either the Browse field or a drag-and-drop,
+    /// dispatching to single-repo enrichment if it's a git repo directly, or to
+    /// repo discovery if it's a parent folder containing one or more nested repos.
+    fn on_path_selected(&mut self, path_str: String, cx: &mut Context<Self>) {
         let path = std::path::PathBuf::from(&path_str);
-        let git_dir = path.join(".git");
 
-        if git_dir.exists() && git_dir.is_dir() {
-            // Valid git repo - proceed to enrichment
-            self.error_message = None;
-            self.on_repo_selected(path_str, cx);
-        } else {
-            // Not a git repo - show error
-            self.error_message = Some(format!(
-                "Not a git repository: {}\nPlease select a folder containing a .git directory",
-                path_str
-            ));
+        if let Some(resolved) = repo_discovery::resolve_repo_root(&path) {
+            let root = match resolved {
+                repo_discovery::ResolvedRepo::Root(root) => root,
+                repo_discovery::ResolvedRepo::Nested { root } => {
+                    eprintln!(
+                        "{} is a submodule/worktree checkout of {} - analyzing the enclosing repository",
+                        path_str, root.display()
+                    );
+                    root
+                }
+            };
+
+            self.controller.error_message = None;
+            self.controller.discovered_repos.clear();
+            self.on_repo_selected(root.to_string_lossy().to_string(), cx);
+            return;
+        }
+
+        if path.is_dir() {
+            let discovered = repo_discovery::discover_repos(&path, &repo_discovery::DiscoveryOptions::default());
+            if discovered.is_empty() {
+                self.controller.error_message = Some(format!(
+                    "No git repositories found under: {}",
+                    path_str
+                ));
+                self.controller.discovered_repos.clear();
+            } else {
+                self.controller.error_message = None;
+                self.controller.discovered_repos = discovered.into_iter()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect();
+            }
             cx.notify();
+            return;
         }
+
+        self.controller.error_message = Some(format!(
+            "No git repository found anywhere above this path: {}\nPlease select a git repository or a folder containing one",
+            path_str
+        ));
+        cx.notify();
+    }
+
+    /// Kicks off batch analysis across every repo `repo_discovery` found under the
+    /// last dropped/browsed parent folder - each repo is analyzed and its
+    /// observation persisted, but no chat session is opened; the user picks a repo
+    /// from "Recent repositories" afterward to start a conversation with it.
+    fn on_analyze_all_clicked(&mut self, cx: &mut Context<Self>) {
+        let repos = self.controller.discovered_repos.clone();
+        if repos.is_empty() {
+            return;
+        }
+
+        self.controller.app_state = AppState::Enriching;
+        self.controller.is_loading = true;
+        self.controller.lifecycle_events.clear();
+        self.controller.lifecycle_events.push(types::LifecycleEvent::running(
+            format!("Analyzing {} repositories", repos.len())
+        ));
+        cx.notify();
+
+        let tokio_handle = self.controller.tokio_handle.clone();
+        let forge_token = self.controller.forge_token.clone();
+        let (tx, rx) = smol::channel::bounded::<(String, f32)>(100);
+
+        let batch_handle = std::thread::spawn(move || {
+            tokio_handle.block_on(async {
+                let total = repos.len();
+                let mut results = Vec::with_capacity(total);
+
+                for (index, repo_path) in repos.into_iter().enumerate() {
+                    let forge_config = forge_token.clone()
+                        .and_then(|token| ForgeConfig::from_repo(&repo_path, token));
+
+                    let tx = tx.clone();
+                    let label = repo_path.clone();
+                    let base_progress = index as f32 / total as f32 * 100.0;
+
+                    let analysis = CodexController::analyze_repo_with_forge(
+                        &repo_path,
+                        forge_config,
+                        move |step, progress| {
+                            let _ = tx.send_blocking((
+                                format!("{}: {}", label, step),
+                                base_progress + progress / total as f32,
+                            ));
+                        },
+                    ).await;
+
+                    results.push((repo_path, analysis));
+                }
+
+                results
+            })
+        });
+
+        cx.spawn(async move |view: WeakEntity<Self>, cx| {
+            while let Ok((step, progress)) = rx.recv().await {
+                view.update(cx, |view, cx| {
+                    view.controller.lifecycle_events.push(types::LifecycleEvent::progress(step, progress));
+                    cx.notify();
+                })?;
+            }
+
+            let results = batch_handle.join().unwrap_or_default();
+
+            view.update(cx, |view, cx| {
+                view.on_batch_analysis_complete(results, cx);
+            })
+        })
+        .detach();
+    }
+
+    fn on_batch_analysis_complete(
+        &mut self,
+        results: Vec<(String, anyhow::Result<types::GitAnalysis>)>,
+        cx: &mut Context<Self>,
+    ) {
+        let mut succeeded = 0;
+        let mut failed = 0;
+
+        for (repo_path, result) in results {
+            match result {
+                Ok(analysis) => {
+                    self.controller.notify_webhook(&repo_path, &analysis);
+                    self.controller.record_observation(&repo_path, &analysis);
+                    succeeded += 1;
+                }
+                Err(e) => {
+                    eprintln!("Batch analysis failed for {}: {}", repo_path, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        self.controller.discovered_repos.clear();
+        self.controller.lifecycle_events.push(types::LifecycleEvent::completed(
+            format!("Batch analysis complete: {} succeeded, {} failed", succeeded, failed)
+        ));
+        self.controller.app_state = AppState::AwaitingRepoSelection;
+        self.controller.is_loading = false;
+        cx.notify();
     }
 
     fn on_repo_selected(&mut self, repo_path: String, cx: &mut Context<Self>) {
-        self.selected_repo = Some(repo_path.clone());
-        self.app_state = AppState::Enriching;
-        self.is_loading = true;
-        self.lifecycle_events.clear();
-        self.messages.clear(); // Clear old messages from previous runs
-        self.lifecycle_events.push(types::LifecycleEvent::running("Scanning git history".to_string()));
+        self.controller.begin_enrichment(repo_path.clone());
         cx.notify();
 
-        // TODO: Make this async once we figure out the GPUI async pattern
-        // For now, doing synchronous enrichment
         eprintln!("Starting git analysis for: {}", repo_path);
 
-        // Note: This blocks the UI thread - not ideal but gets us working
-        // We'll optimize with proper async later
-        let tokio_handle = self.tokio_handle.clone();
+        // Only the initial metadata read touches the locked libgit2 handle - the
+        // commit scan below and the full analysis run as `git` subprocesses so a
+        // very large history never holds this lock while it streams.
+        if let Ok(repo) = Libgit2GitRepository::open(&repo_path) {
+            match repo.head_sha() {
+                Ok(sha) => eprintln!("HEAD: {}", sha),
+                Err(e) => eprintln!("Failed to read HEAD: {}", e),
+            }
+        }
+
+        let tokio_handle = self.controller.tokio_handle.clone();
+
+        // Stream (step, progress) updates off the analysis thread, same as
+        // `on_send_message`'s StreamEvent channel - keeps the UI thread free
+        // to repaint the progress bar while the background thread walks the
+        // commit history.
+        let (tx, rx) = smol::channel::bounded::<(String, f32)>(100);
+
+        // Derived from `origin` up front (synchronous, just reads local git config) so
+        // the background thread doesn't need a reference back to `self`.
+        let forge_config = self.controller.forge_token.clone()
+            .and_then(|token| ForgeConfig::from_repo(&repo_path, token));
+
+        let scan_repo_path = repo_path.clone();
+        let analysis_repo_path = repo_path.clone();
+        let evidence_repo_path = repo_path.clone();
+        let analysis_handle = std::thread::spawn(move || {
+            tokio_handle.block_on(async move {
+                // Stream `git log` into a live running count before the full
+                // (blocking, libgit2-based) pattern analysis runs, so page 2 shows
+                // real commits scrolling by instead of sitting on one fixed label
+                // for however long the history scan takes.
+                let scan_repo = SubprocessGitRepository::new(&scan_repo_path);
+                let scan_limit = 100;
+                if let Ok(total) = scan_repo.commit_count(scan_limit) {
+                    let tx_scan = tx.clone();
+                    let mut scanned = 0usize;
+                    let _ = scan_repo.log_streaming(scan_limit, move |commit| {
+                        scanned += 1;
+                        let short_sha = &commit.sha[..commit.sha.len().min(8)];
+                        let _ = tx_scan.send_blocking((
+                            format!("Scanned {}/{}: {} {}", scanned, total, short_sha, commit.message),
+                            scanned as f32 / total.max(1) as f32 * 100.0,
+                        ));
+                    });
+                }
+
+                let tx_analysis = tx.clone();
+                let analysis = CodexController::analyze_repo_with_forge(
+                    &analysis_repo_path,
+                    forge_config,
+                    move |step, progress| {
+                        let _ = tx_analysis.send_blocking((step, progress));
+                    },
+                ).await?;
+
+                // Incremental semantic indexing runs after the git analysis itself,
+                // over the same progress channel, so page 2's checklist keeps
+                // showing real work ("Embedding commit 12/40") instead of going
+                // idle while the model citations are retrieved.
+                let evidence = CodexController::gather_semantic_evidence(
+                    &evidence_repo_path,
+                    move |step, progress| {
+                        let _ = tx.send_blocking((step, progress));
+                    },
+                ).await;
+
+                Ok::<_, anyhow::Error>((analysis, evidence))
+            })
+        });
+
+        cx.spawn(async move |view: WeakEntity<Self>, cx| {
+            while let Ok((step, progress)) = rx.recv().await {
+                view.update(cx, |view, cx| {
+                    view.controller.lifecycle_events.push(types::LifecycleEvent::progress(step, progress));
+                    cx.notify();
+                })?;
+            }
+
+            let analysis_result = analysis_handle.join().unwrap_or_else(|_| {
+                Err(anyhow::anyhow!("Git analysis thread panicked"))
+            });
 
-        // For now, use a no-op progress callback (we'll add UI integration later)
-        let analysis_result = tokio_handle.block_on(GitAnalyzer::analyze(&repo_path, |_step, _progress| {
-            // Progress callback - will integrate with UI later
-        }));
+            view.update(cx, |view, cx| {
+                view.on_git_analysis_complete(repo_path, analysis_result, cx);
+            })
+        })
+        .detach();
+    }
 
+    fn on_git_analysis_complete(
+        &mut self,
+        repo_path: String,
+        analysis_result: anyhow::Result<(types::GitAnalysis, Vec<semantic_index::RetrievedChunk>)>,
+        cx: &mut Context<Self>,
+    ) {
         match analysis_result {
-            Ok(analysis) => {
+            Ok((analysis, evidence)) => {
                 eprintln!("Git analysis complete: {} commits analyzed, {} patterns found",
                     analysis.total_commits_analyzed, analysis.patterns.len());
 
-                // Build system prompt from analysis
-                let patterns_summary = if analysis.patterns.is_empty() {
-                    "No significant behavioral patterns detected in git history.".to_string()
-                } else {
-                    analysis.patterns.iter()
-                        .map(|p| format!("• {}: {}", p.title, p.description))
-                        .collect::<Vec<_>>()
-                        .join("\n")
-                };
-
-                let system_prompt = format!(
-                    "You are a developer psychologist practicing evidence-based conversational archaeology.\n\n\
-                     ## GIT COMMIT PATTERNS (from {} commits analyzed, severity {:.1}/1.0)\n\n\
-                     {}\n\n\
-                     ## YOUR MISSION: GUIDE USERS TO ENRICH THEIR PROJECT\n\n\
-                     You have MCP tools to analyze git patterns. Use them to:\n\
-                     1. Surface blindspots users can't see themselves\n\
-                     2. Ask questions that make them reflect deeply\n\
-                     3. Guide them toward actionable improvements\n\
-                     4. Build longitudinal understanding across sessions\n\n\
-                     ## CONVERSATION STRATEGY (Socratic Guidance)\n\n\
-                     **Phase 1: Discovery** (Current - gather context)\n\
-                     - Ask about: project goals, team structure, customer, timeline\n\
-                     - Use their answers to understand MOTIVATION and CONSTRAINTS\n\
-                     - Build rapport through genuine curiosity\n\
-                     - Listen for what they DON'T say\n\n\
-                     **Phase 2: Investigation** (use MCP tools)\n\
-                     When you have context, use tools to dig deeper:\n\
-                     - `analyze_commit_patterns` - find commitment issues\n\
-                     - `analyze_message_language` - detect minimizing/defensive patterns\n\
-                     - `compare_message_vs_diff` - spot self-deception\n\
-                     - `get_temporal_patterns` - reveal stress/overwork\n\
-                     - `get_repo_context` - access memory from past sessions\n\n\
-                     **Phase 3: Observation** (synthesize evidence)\n\
-                     Create a 3-4 sentence observation:\n\
-                     1. Cite EXACT git numbers (\"47 commits at night = 62%\")\n\
-                     2. Connect to their stated goals (\"but you said X...\")\n\
-                     3. Name the pattern (\"This suggests Y anti-pattern\")\n\
-                     4. Ask ONE pointed question about the blindspot\n\n\
-                     **Phase 4: Guidance** (lead toward action)\n\
-                     Based on their response:\n\
-                     - Validate their awareness\n\
-                     - Suggest concrete experiments\n\
-                     - Use `flag_repo_issue` to track the pattern\n\
-                     - Offer to check back next session\n\n\
-                     ## ABSOLUTE RULES\n\n\
-                     - DO NOT read, analyze, or reference source code files\n\
-                     - DO NOT do code review or technical assessment\n\
-                     - Focus on BEHAVIOR patterns, not code quality\n\
-                     - Use EXACT numbers from git data (never approximate)\n\
-                     - Be conversational and empathetic - therapist, not linter\n\
-                     - Each question should make them think deeper about their project\n\n\
-                     **Your goal: Guide them to insights they'd never find alone. Make them WANT to share more about their project.**",
-                    analysis.total_commits_analyzed,
-                    analysis.severity,
-                    patterns_summary
-                );
+                self.controller.notify_webhook(&repo_path, &analysis);
+
+                let prior_analysis = self.controller.record_observation(&repo_path, &analysis);
+                let system_prompt = CodexController::build_system_prompt(&repo_path, &analysis, prior_analysis.as_ref(), &evidence);
 
                 // Initialize Codex
                 match CodexAdapter::new() {
@@ -249,9 +481,9 @@ impl CodexView {
                         // Spawn codex-acp
                         if let Err(e) = adapter.spawn() {
                             eprintln!("Failed to spawn codex-acp: {}", e);
-                            self.error_message = Some(format!("Failed to start Codex: {}", e));
-                            self.app_state = AppState::AwaitingRepoSelection;
-                            self.is_loading = false;
+                            self.controller.error_message = Some(format!("Failed to start Codex: {}", e));
+                            self.controller.app_state = AppState::AwaitingRepoSelection;
+                            self.controller.is_loading = false;
                             cx.notify();
                             return;
                         }
@@ -259,9 +491,9 @@ impl CodexView {
                         // Initialize ACP
                         if let Err(e) = adapter.initialize() {
                             eprintln!("Failed to initialize ACP: {}", e);
-                            self.error_message = Some(format!("Failed to initialize Codex: {}", e));
-                            self.app_state = AppState::AwaitingRepoSelection;
-                            self.is_loading = false;
+                            self.controller.error_message = Some(format!("Failed to initialize Codex: {}", e));
+                            self.controller.app_state = AppState::AwaitingRepoSelection;
+                            self.controller.is_loading = false;
                             cx.notify();
                             return;
                         }
@@ -272,11 +504,11 @@ impl CodexView {
                                 eprintln!("Codex session created: {}", session_id);
 
                                 // Success!
-                                self.codex_adapter = Some(adapter.clone());
-                                self.lifecycle_events.push(types::LifecycleEvent::completed("Git analysis".to_string()));
-                                self.lifecycle_events.push(types::LifecycleEvent::completed("AI initialized".to_string()));
-                                self.app_state = AppState::ChatActive;
-                                self.is_loading = false;
+                                self.controller.codex_adapter = Some(adapter.clone());
+                                self.controller.lifecycle_events.push(types::LifecycleEvent::completed("Git analysis".to_string()));
+                                self.controller.lifecycle_events.push(types::LifecycleEvent::completed("AI initialized".to_string()));
+                                self.controller.app_state = AppState::ChatActive;
+                                self.controller.is_loading = false;
                                 cx.notify();
 
                                 // Create beautiful discovery greeting (Perplexity-style)
@@ -284,66 +516,39 @@ impl CodexView {
                                 eprintln!("📊 Git patterns ready for synthesis");
                                 eprintln!("🔧 MCP tools: codex-psychology available at :52848");
 
-                                // Generate contextual discovery greeting based on git patterns
-                                let pattern_count = analysis.patterns.len();
-                                let commit_count = analysis.total_commits_analyzed;
-
-                                let discovery_greeting = if pattern_count > 0 {
-                                    let top_pattern = &analysis.patterns[0];
-                                    format!(
-                                        "## 🔍 Analysis Complete\n\n\
-                                         I've analyzed **{} commits** and discovered **{} behavioral patterns**.\n\n\
-                                         Most notable: *{}*\n\n\
-                                         Before I share my observations, I'd like to understand the context.\n\n\
-                                         **Tell me about this project:**\n\
-                                         - What are you building?\n\
-                                         - Who's working on it?\n\
-                                         - What's the goal?",
-                                        commit_count,
-                                        pattern_count,
-                                        top_pattern.title
-                                    )
-                                } else {
-                                    format!(
-                                        "## 👋 Let's Explore Your Code\n\n\
-                                         I've analyzed **{} commits** from your repository.\n\n\
-                                         To give you meaningful insights, I need to understand:\n\n\
-                                         **What is this project?** Tell me about what you're building and who it's for.",
-                                        commit_count
-                                    )
-                                };
+                                let discovery_greeting = CodexController::discovery_greeting(&analysis);
 
                                 // Add beautiful discovery greeting to timeline
-                                self.timeline_events.push(TimelineEvent::AssistantMessage {
+                                self.controller.timeline_events.push(TimelineEvent::AssistantMessage {
                                     content: discovery_greeting,
-                                    timestamp: chrono::Utc::now().timestamp(),
+                                    timestamp: now_timestamp(),
                                 });
 
                                 eprintln!("✅ Discovery phase ready - Claude will use MCP tools when user responds");
                             }
                             Err(e) => {
                                 eprintln!("Failed to create session: {}", e);
-                                self.error_message = Some(format!("Failed to create session: {}", e));
-                                self.app_state = AppState::AwaitingRepoSelection;
-                                self.is_loading = false;
+                                self.controller.error_message = Some(format!("Failed to create session: {}", e));
+                                self.controller.app_state = AppState::AwaitingRepoSelection;
+                                self.controller.is_loading = false;
                                 cx.notify();
                             }
                         }
                     }
                     Err(e) => {
                         eprintln!("Failed to create CodexAdapter: {}", e);
-                        self.error_message = Some(format!("Failed to create Codex: {}", e));
-                        self.app_state = AppState::AwaitingRepoSelection;
-                        self.is_loading = false;
+                        self.controller.error_message = Some(format!("Failed to create Codex: {}", e));
+                        self.controller.app_state = AppState::AwaitingRepoSelection;
+                        self.controller.is_loading = false;
                         cx.notify();
                     }
                 }
             }
             Err(e) => {
                 eprintln!("Git analysis error: {}", e);
-                self.error_message = Some(format!("Git analysis failed: {}", e));
-                self.app_state = AppState::AwaitingRepoSelection;
-                self.is_loading = false;
+                self.controller.error_message = Some(format!("Git analysis failed: {}", e));
+                self.controller.app_state = AppState::AwaitingRepoSelection;
+                self.controller.is_loading = false;
                 cx.notify();
             }
         }
@@ -362,28 +567,28 @@ impl CodexView {
         });
 
         // Add user message to timeline
-        let now = chrono::Utc::now().timestamp();
+        let now = now_timestamp();
         let user_event = TimelineEvent::UserMessage {
             content: content.clone(),
             timestamp: now,
         };
-        self.timeline_events.push(user_event);
+        self.controller.timeline_events.push(user_event);
 
         // Also save to old messages vec for storage
         let message = Message::user(content.clone());
-        self.messages.push(message.clone());
+        self.controller.messages.push(message.clone());
 
-        if let Some(storage) = &self.storage {
+        if let Some(storage) = &self.controller.storage {
             let _ = storage.save_message(&message);
         }
 
         cx.notify();
 
         // Send to Codex and stream response asynchronously
-        if let Some(adapter) = &self.codex_adapter {
+        if let Some(adapter) = &self.controller.codex_adapter {
             let adapter = adapter.clone();
-            let storage = self.storage.clone();
-            let tokio_handle = self.tokio_handle.clone();
+            let storage = self.controller.storage.clone();
+            let tokio_handle = self.controller.tokio_handle.clone();
 
             // Create channel for streaming
             let (tx, rx) = smol::channel::bounded::<types::StreamEvent>(100);
@@ -413,19 +618,19 @@ impl CodexView {
                     match event {
                         types::StreamEvent::MessageChunk(chunk) => {
                             view.update(cx, |view, cx| {
-                                view.current_message_buffer.push_str(&chunk);
+                                view.controller.current_message_buffer.push_str(&chunk);
                                 cx.notify();
                             })?;
                         }
                         types::StreamEvent::ThoughtChunk(chunk) => {
                             view.update(cx, |view, cx| {
-                                view.current_thought_buffer.push_str(&chunk);
+                                view.controller.current_thought_buffer.push_str(&chunk);
                                 cx.notify();
                             })?;
                         }
                         types::StreamEvent::ToolCall(tool_call) => {
                             view.update(cx, |view, cx| {
-                                view.active_tool_calls.insert(
+                                view.controller.active_tool_calls.insert(
                                     tool_call.tool_call_id.clone(),
                                     (tool_call, String::new())
                                 );
@@ -434,7 +639,7 @@ impl CodexView {
                         }
                         types::StreamEvent::ToolCallUpdate(update) => {
                             view.update(cx, |view, cx| {
-                                if let Some((tool_call, output)) = view.active_tool_calls.get_mut(&update.tool_call_id) {
+                                if let Some((tool_call, output)) = view.controller.active_tool_calls.get_mut(&update.tool_call_id) {
                                     if let Some(status) = &update.status {
                                         tool_call.status = status.clone();
                                     }
@@ -445,26 +650,41 @@ impl CodexView {
                                 cx.notify();
                             })?;
                         }
+                        types::StreamEvent::FileWrite(event) => {
+                            eprintln!("Agent wrote {} bytes to {}", event.bytes_written, event.path);
+                        }
+                        types::StreamEvent::TerminalOutput(event) => {
+                            eprintln!("[terminal {}] {}", event.terminal_id, event.output.trim_end());
+                        }
+                        types::StreamEvent::ConnectionReconnecting(event) => {
+                            eprintln!("Reconnecting to codex-acp (attempt {}/{})", event.attempt, event.max_attempts);
+                        }
+                        types::StreamEvent::ConnectionReconnected => {
+                            eprintln!("Reconnected to codex-acp");
+                        }
+                        types::StreamEvent::ConnectionFailed(reason) => {
+                            eprintln!("codex-acp connection failed permanently: {}", reason);
+                        }
                         _ => {}
                     }
                 }
 
                 // Stream complete - convert buffers to timeline events
                 view.update(cx, |view, cx| {
-                    let now = chrono::Utc::now().timestamp();
+                    let now = now_timestamp();
 
                     // Add thought to timeline if present
-                    if !view.current_thought_buffer.is_empty() {
-                        view.timeline_events.push(TimelineEvent::Thought {
-                            content: view.current_thought_buffer.clone(),
+                    if !view.controller.current_thought_buffer.is_empty() {
+                        view.controller.timeline_events.push(TimelineEvent::Thought {
+                            content: view.controller.current_thought_buffer.clone(),
                             timestamp: now,
                         });
-                        view.current_thought_buffer.clear();
+                        view.controller.current_thought_buffer.clear();
                     }
 
                     // Add tool calls to timeline
-                    for (_id, (tool_call, output)) in view.active_tool_calls.drain() {
-                        view.timeline_events.push(TimelineEvent::ToolCall {
+                    for (_id, (tool_call, output)) in view.controller.active_tool_calls.drain() {
+                        view.controller.timeline_events.push(TimelineEvent::ToolCall {
                             tool_call_id: tool_call.tool_call_id,
                             title: tool_call.title,
                             kind: tool_call.kind,
@@ -478,9 +698,9 @@ impl CodexView {
                     }
 
                     // Add assistant message to timeline if present
-                    if !view.current_message_buffer.is_empty() {
-                        let msg_content = view.current_message_buffer.clone();
-                        view.timeline_events.push(TimelineEvent::AssistantMessage {
+                    if !view.controller.current_message_buffer.is_empty() {
+                        let msg_content = view.controller.current_message_buffer.clone();
+                        view.controller.timeline_events.push(TimelineEvent::AssistantMessage {
                             content: msg_content.clone(),
                             timestamp: now,
                         });
@@ -491,7 +711,7 @@ impl CodexView {
                             let _ = storage.save_message(&msg);
                         }
 
-                        view.current_message_buffer.clear();
+                        view.controller.current_message_buffer.clear();
                     }
 
                     cx.notify();
@@ -501,9 +721,9 @@ impl CodexView {
             eprintln!("No Codex adapter available");
             let error_event = TimelineEvent::AssistantMessage {
                 content: "Error: Codex not initialized".to_string(),
-                timestamp: chrono::Utc::now().timestamp(),
+                timestamp: now_timestamp(),
             };
-            self.timeline_events.push(error_event);
+            self.controller.timeline_events.push(error_event);
             cx.notify();
         }
     }
@@ -518,7 +738,7 @@ impl Render for CodexView {
             .flex_col()
             .size_full()
             .bg(bg_primary)
-            .child(match self.app_state {
+            .child(match self.controller.app_state {
                 AppState::AwaitingRepoSelection => self.render_page_1(cx),
                 AppState::Enriching => self.render_page_2(cx),
                 AppState::ChatActive => self.render_page_3(cx),
@@ -541,7 +761,7 @@ impl CodexView {
             .gap_4();
 
         // Show error message if present
-        if let Some(error) = &self.error_message {
+        if let Some(error) = &self.controller.error_message {
             page = page.child(
                 div()
                     .w(px(500.0))
@@ -556,7 +776,7 @@ impl CodexView {
             );
         }
 
-        page.child(
+        page = page.child(
                 div()
                     .text_3xl()
                     .font_weight(FontWeight::BOLD)
@@ -584,23 +804,12 @@ impl CodexView {
                     .justify_center()
                     .gap_3()
                     .on_drop(cx.listener(|this, external_paths: &ExternalPaths, _, cx| {
-                        // Extract the first path from dropped files
+                        // A single repo is analyzed directly; a parent folder is walked
+                        // for nested repos and offered as a batch-analysis candidate list.
                         if let Some(path) = external_paths.paths().first() {
                             let path_str = path.to_string_lossy().to_string();
                             eprintln!("Dropped path: {}", path_str);
-
-                            // Validate it's a git repo
-                            let git_dir = path.join(".git");
-                            if git_dir.exists() && git_dir.is_dir() {
-                                this.error_message = None;
-                                this.on_repo_selected(path_str, cx);
-                            } else {
-                                this.error_message = Some(format!(
-                                    "Not a git repository: {}\nPlease drop a folder containing a .git directory",
-                                    path_str
-                                ));
-                                cx.notify();
-                            }
+                            this.on_path_selected(path_str, cx);
                         }
                     }))
                     .child(
@@ -639,10 +848,153 @@ impl CodexView {
                                 });
                             })
                     })
-            )
+            );
+
+        // Recent repositories, backed by the observations store, so a user can jump
+        // straight back into an ongoing conversation instead of re-analyzing from scratch.
+        let recent_repos = self.controller.storage.as_ref()
+            .and_then(|storage| storage.list_recent_repos(5).ok())
+            .unwrap_or_default();
+
+        if !recent_repos.is_empty() {
+            page = page.child(
+                div()
+                    .mt_4()
+                    .w(px(500.0))
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(rgb(0x999999))
+                            .child("Recent repositories")
+                    )
+                    .children(recent_repos.into_iter().map(|repo_path| {
+                        let view = cx.entity().clone();
+                        let click_path = repo_path.clone();
+                        div()
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(bg_surface)
+                            .text_sm()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0xeaeaea)))
+                            .on_click(move |_event, _window, cx| {
+                                view.update(cx, |view, cx| {
+                                    view.on_repo_selected(click_path.clone(), cx);
+                                });
+                            })
+                            .child(repo_path)
+                    }))
+            );
+        }
+
+        // Repos found under a dropped/browsed parent folder - offered as a batch
+        // before any single-repo analysis kicks off.
+        if !self.controller.discovered_repos.is_empty() {
+            let repo_count = self.controller.discovered_repos.len();
+            page = page.child(
+                div()
+                    .mt_4()
+                    .w(px(500.0))
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_between()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(rgb(0x999999))
+                                    .child(format!("Discovered repositories ({})", repo_count))
+                            )
+                            .child({
+                                let view = cx.entity().clone();
+                                Button::new("analyze-all-button")
+                                    .label("Analyze all")
+                                    .primary()
+                                    .on_click(move |_, _window, cx| {
+                                        view.update(cx, |view, cx| {
+                                            view.on_analyze_all_clicked(cx);
+                                        });
+                                    })
+                            })
+                    )
+                    .children(self.controller.discovered_repos.clone().into_iter().map(|repo_path| {
+                        let view = cx.entity().clone();
+                        let click_path = repo_path.clone();
+                        div()
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(bg_surface)
+                            .text_sm()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0xeaeaea)))
+                            .on_click(move |_event, _window, cx| {
+                                view.update(cx, |view, cx| {
+                                    view.on_repo_selected(click_path.clone(), cx);
+                                });
+                            })
+                            .child(repo_path)
+                    }))
+            );
+        }
+
+        // Optional forge connection - enables PR collaboration patterns (review
+        // latency, self-merge rate) alongside the local commit-history patterns.
+        page = page.child(
+            div()
+                .mt_4()
+                .w(px(500.0))
+                .flex()
+                .flex_col()
+                .gap_2()
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(rgb(0x999999))
+                        .child("Connect a forge (optional)")
+                )
+                .child(
+                    if let Some(token) = &self.controller.forge_token {
+                        div()
+                            .text_sm()
+                            .text_color(rgb(0x2e7d32))
+                            .child(format!("Connected ({} chars token stored)", token.len()))
+                    } else {
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(
+                                TextInput::new(&self.forge_token_input)
+                                    .w(px(350.0))
+                            )
+                            .child({
+                                let view = cx.entity().clone();
+                                Button::new("connect-forge-button")
+                                    .label("Connect")
+                                    .on_click(move |_, window, cx| {
+                                        view.update(cx, |view, cx| {
+                                            view.on_connect_forge_clicked(window, cx);
+                                        });
+                                    })
+                            })
+                    }
+                )
+        );
+
+        page
     }
 
     fn render_page_2(&mut self, _cx: &mut Context<Self>) -> Div {
+        let progress = self.controller.lifecycle_events.iter().rev().find_map(|e| e.progress).unwrap_or(0.0);
+
         div()
             .flex()
             .flex_col()
@@ -655,27 +1007,40 @@ impl CodexView {
                     .font_weight(FontWeight::BOLD)
                     .child(format!(
                         "Analyzing {}",
-                        self.selected_repo.as_deref().unwrap_or("repository")
+                        self.controller.selected_repo.as_deref().unwrap_or("repository")
                     ))
             )
             .child(
+                // Progress bar, driven by the most recent `LifecycleEvent::progress`.
                 div()
-                    .flex()
-                    .flex_col()
-                    .gap_2()
-                    .child(
-                        div()
-                            .child("⏳ Enriching context...")
-                    )
+                    .w_full()
+                    .h(px(6.0))
+                    .rounded_full()
+                    .bg(rgb(0xe0e0e0))
+                    .overflow_hidden()
                     .child(
                         div()
-                            .child("✅ Git history scanned")
-                    )
-                    .child(
-                        div()
-                            .child("🔄 Generating observation...")
+                            .h_full()
+                            .rounded_full()
+                            .bg(rgb(0x1976d2))
+                            .w(relative(progress / 100.0))
                     )
             )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .children(self.controller.lifecycle_events.iter().map(|event| {
+                        let icon = match event.status {
+                            types::LifecycleStatus::Pending => "⏳",
+                            types::LifecycleStatus::Running => "🔄",
+                            types::LifecycleStatus::Completed => "✅",
+                            types::LifecycleStatus::Failed => "❌",
+                        };
+                        div().child(format!("{} {}", icon, event.tool_name))
+                    }))
+            )
     }
 
     fn render_page_3(&mut self, cx: &mut Context<Self>) -> Div {
@@ -706,33 +1071,44 @@ impl CodexView {
                         div()
                             .text_sm()
                             .text_color(rgb(0x666666))
-                            .child(self.selected_repo.clone().unwrap_or_default())
+                            .child(self.controller.selected_repo.clone().unwrap_or_default())
                     )
             )
-            .child(
-                // Timeline area (Perplexity-style trajectory view) - scrollable
+            .child({
+                // Keep the virtualized widget's backing list in sync with
+                // `controller.timeline_events` (the source of truth) before rendering it.
+                let events = self.controller.timeline_events.clone();
+                self.timeline_widget.update(cx, |widget, cx| {
+                    if widget.events().len() != events.len() {
+                        widget.set_events(events, cx);
+                    }
+                });
+
+                // Timeline area (Perplexity-style trajectory view) - the
+                // widget owns its own scrolling via `uniform_list`.
                 div()
                     .id("timeline-container")
                     .flex_1()
-                    .overflow_y_scroll()
-                    .track_scroll(&self.timeline_scroll_handle)
                     .child(
                         // Inner content div (not flex container!)
                         div()
+                            .size_full()
                             .p_6()
-                            .child(render_timeline(&self.timeline_events))
+                            .flex()
+                            .flex_col()
+                            .child(div().flex_1().child(self.timeline_widget.clone()))
                             // Add streaming views for active buffers
-                            .when(!self.current_thought_buffer.is_empty(), |parent| {
-                                parent.child(render_streaming_thought(&self.current_thought_buffer))
+                            .when(!self.controller.current_thought_buffer.is_empty(), |parent| {
+                                parent.child(render_streaming_thought(&self.controller.current_thought_buffer, cx))
                             })
-                            .when(!self.current_message_buffer.is_empty(), |parent| {
-                                parent.child(render_streaming_message(&self.current_message_buffer))
+                            .when(!self.controller.current_message_buffer.is_empty(), |parent| {
+                                parent.child(render_streaming_message(&self.controller.current_message_buffer, cx))
                             })
-                            .children(self.active_tool_calls.iter().map(|(_, (tool_call, output))| {
-                                render_streaming_tool_call(tool_call, output)
+                            .children(self.controller.active_tool_calls.iter().map(|(_, (tool_call, output))| {
+                                render_streaming_tool_call(tool_call, output, cx)
                             }))
                     )
-            )
+            })
             .child(
                 // Input area
                 div()
@@ -0,0 +1,171 @@
+// Project-manifest detection - reads Cargo.toml/package.json/pyproject.toml/go.mod well
+// enough to pull out a name, description, and direct dependency list, then folds that into
+// the enrichment system prompt as a "## PROJECT MANIFEST" section. Manifests only, never
+// source files, so this doesn't cross into the code-review territory the psychologist
+// prompt explicitly avoids.
+
+use std::fs;
+use std::path::Path;
+
+/// A manifest's name/description/dependency summary.
+#[derive(Debug, Clone)]
+pub struct ProjectContext {
+    pub manifest_kind: &'static str,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub dependencies: Vec<String>,
+}
+
+impl ProjectContext {
+    /// Tries each supported manifest in turn, returning the first one found at the
+    /// repo root. Cargo.toml is checked first since this is primarily a Rust tool.
+    pub fn detect(repo_path: &str) -> Option<Self> {
+        let root = Path::new(repo_path);
+        Self::from_cargo_toml(root)
+            .or_else(|| Self::from_package_json(root))
+            .or_else(|| Self::from_pyproject_toml(root))
+            .or_else(|| Self::from_go_mod(root))
+    }
+
+    /// Renders the `## PROJECT MANIFEST` section folded into the system prompt.
+    pub fn to_prompt_section(&self) -> String {
+        let mut section = format!("## PROJECT MANIFEST ({})\n\n", self.manifest_kind);
+        if let Some(name) = &self.name {
+            section.push_str(&format!("Name: {}\n", name));
+        }
+        if let Some(description) = &self.description {
+            section.push_str(&format!("Description: {}\n", description));
+        }
+        if !self.dependencies.is_empty() {
+            section.push_str(&format!("Direct dependencies: {}\n", self.dependencies.join(", ")));
+        }
+        section
+    }
+
+    fn from_cargo_toml(root: &Path) -> Option<Self> {
+        let content = fs::read_to_string(root.join("Cargo.toml")).ok()?;
+
+        let mut name = None;
+        let mut description = None;
+        let mut dependencies = Vec::new();
+        let mut section = "";
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = if header == "dependencies" { "dependencies" } else { "" };
+                continue;
+            }
+
+            match (section, toml_key_value(line)) {
+                ("", Some(("name", value))) if name.is_none() => name = Some(value),
+                ("", Some(("description", value))) => description = Some(value),
+                ("dependencies", Some((key, _))) => dependencies.push(key.to_string()),
+                _ => {}
+            }
+        }
+
+        (name.is_some() || !dependencies.is_empty())
+            .then_some(Self { manifest_kind: "Cargo.toml", name, description, dependencies })
+    }
+
+    fn from_package_json(root: &Path) -> Option<Self> {
+        let content = fs::read_to_string(root.join("package.json")).ok()?;
+        let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+        let name = value.get("name").and_then(|v| v.as_str()).map(str::to_string);
+        let description = value.get("description").and_then(|v| v.as_str()).map(str::to_string);
+        let dependencies = value
+            .get("dependencies")
+            .and_then(|v| v.as_object())
+            .map(|deps| deps.keys().cloned().collect())
+            .unwrap_or_default();
+
+        Some(Self { manifest_kind: "package.json", name, description, dependencies })
+    }
+
+    fn from_pyproject_toml(root: &Path) -> Option<Self> {
+        let content = fs::read_to_string(root.join("pyproject.toml")).ok()?;
+
+        let mut name = None;
+        let mut description = None;
+        let mut dependencies = Vec::new();
+        let mut section = "";
+        let mut in_dependencies_array = false;
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = if header == "project" || header == "tool.poetry" { "project" } else { "" };
+                continue;
+            }
+
+            if section != "project" {
+                continue;
+            }
+
+            if in_dependencies_array {
+                if line.starts_with(']') {
+                    in_dependencies_array = false;
+                } else if let Some(dep) = line
+                    .trim_matches(|c: char| c == ',' || c == '"')
+                    .split(|c: char| c == '=' || c == '<' || c == '>' || c == '~')
+                    .next()
+                {
+                    let dep = dep.trim();
+                    if !dep.is_empty() {
+                        dependencies.push(dep.to_string());
+                    }
+                }
+                continue;
+            }
+
+            match toml_key_value(line) {
+                Some(("name", value)) => name = Some(value),
+                Some(("description", value)) => description = Some(value),
+                _ if line.starts_with("dependencies") && line.contains('[') => {
+                    in_dependencies_array = !line.contains(']');
+                }
+                _ => {}
+            }
+        }
+
+        (name.is_some() || !dependencies.is_empty())
+            .then_some(Self { manifest_kind: "pyproject.toml", name, description, dependencies })
+    }
+
+    fn from_go_mod(root: &Path) -> Option<Self> {
+        let content = fs::read_to_string(root.join("go.mod")).ok()?;
+
+        let mut name = None;
+        let mut dependencies = Vec::new();
+        let mut in_require_block = false;
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
+            if let Some(module) = line.strip_prefix("module ") {
+                name = Some(module.trim().to_string());
+            } else if line.starts_with("require (") {
+                in_require_block = true;
+            } else if in_require_block && line.starts_with(')') {
+                in_require_block = false;
+            } else if in_require_block || line.starts_with("require ") {
+                let entry = line.strip_prefix("require ").unwrap_or(line);
+                if let Some(module) = entry.split_whitespace().next() {
+                    dependencies.push(module.to_string());
+                }
+            }
+        }
+
+        (name.is_some() || !dependencies.is_empty())
+            .then_some(Self { manifest_kind: "go.mod", name, description: None, dependencies })
+    }
+}
+
+/// Parses a bare `key = "value"` TOML line, stripping surrounding quotes.
+fn toml_key_value(line: &str) -> Option<(&str, String)> {
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim();
+    let value = value.trim().trim_matches('"');
+    (!key.is_empty() && !value.is_empty()).then(|| (key, value.to_string()))
+}
@@ -0,0 +1,140 @@
+// Recursive discovery of git repositories under a parent directory, so dropping a
+// whole projects folder onto page 1 can analyze every repo it contains in one pass
+// instead of requiring the user to drop each repo individually.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Tunable parameters for `discover_repos`.
+#[derive(Debug, Clone)]
+pub struct DiscoveryOptions {
+    /// How many directory levels to descend below the root before giving up on a
+    /// branch. Keeps a deep, repo-free directory tree (e.g. `node_modules`) from
+    /// stalling the walk.
+    pub max_depth: usize,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self { max_depth: 6 }
+    }
+}
+
+/// Walks `root` looking for directories containing a `.git` entry. Stops descending
+/// once a repo is found (the repo's own internals aren't walked), and dedupes by the
+/// resolved git directory so worktrees sharing the same underlying `.git` are only
+/// reported once. Returns repo root paths in traversal order.
+pub fn discover_repos(root: &Path, options: &DiscoveryOptions) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut seen_git_dirs = HashSet::new();
+    walk(root, 0, options, &mut seen_git_dirs, &mut found);
+    found
+}
+
+fn walk(
+    dir: &Path,
+    depth: usize,
+    options: &DiscoveryOptions,
+    seen_git_dirs: &mut HashSet<PathBuf>,
+    found: &mut Vec<PathBuf>,
+) {
+    let dot_git = dir.join(".git");
+    if dot_git.exists() {
+        let resolved = resolve_git_dir(&dot_git);
+        if seen_git_dirs.insert(resolved) {
+            found.push(dir.to_path_buf());
+        }
+        // Don't recurse into a repo's own working tree - nested `.git` directories
+        // belong to submodules, which are discovered independently if the walk
+        // reaches them some other way.
+        return;
+    }
+
+    if depth >= options.max_depth {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        // Hidden directories (`.cache`, `.cargo`, ...) are never project roots.
+        if path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.')) {
+            continue;
+        }
+        walk(&path, depth + 1, options, seen_git_dirs, found);
+    }
+}
+
+/// Outcome of resolving a user-supplied path to the git repository that owns it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedRepo {
+    /// `path` is itself a repository root - ordinary, linked worktree, or bare.
+    Root(PathBuf),
+    /// `path` is nested inside `root` (a submodule checkout or a subdirectory of a
+    /// worktree), which is the enclosing repository to actually analyze.
+    Nested { root: PathBuf },
+}
+
+/// Resolves `path` to the git repository that owns it, checking `path` itself and
+/// then walking upward through its ancestors. Recognizes ordinary repos (`.git` is
+/// a directory), linked worktrees and submodule checkouts (`.git` is a file with a
+/// valid `gitdir:` pointer), and bare repos (`HEAD` + `objects/` + `refs/` present
+/// with no working tree at all). Returns `None` if no repository is found anywhere
+/// above `path`.
+pub fn resolve_repo_root(path: &Path) -> Option<ResolvedRepo> {
+    if is_repo_root(path) {
+        return Some(ResolvedRepo::Root(path.to_path_buf()));
+    }
+
+    let mut ancestor = path.parent();
+    while let Some(dir) = ancestor {
+        if is_repo_root(dir) {
+            return Some(ResolvedRepo::Nested { root: dir.to_path_buf() });
+        }
+        ancestor = dir.parent();
+    }
+
+    None
+}
+
+fn is_repo_root(dir: &Path) -> bool {
+    let dot_git = dir.join(".git");
+
+    if dot_git.is_dir() {
+        return true;
+    }
+
+    if dot_git.is_file() {
+        // A `.git` file with a `gitdir:` pointer that doesn't resolve to anything
+        // real is a broken checkout, not a repository worth analyzing.
+        return resolve_git_dir(&dot_git).exists();
+    }
+
+    // Bare repo: no working tree, just the git directory's own contents at `dir`.
+    dir.join("HEAD").is_file() && dir.join("objects").is_dir() && dir.join("refs").is_dir()
+}
+
+/// Resolves a `.git` entry to the actual git directory it points at. Ordinary repos
+/// have `.git` as a directory already; worktrees have it as a file containing
+/// `gitdir: <path>`, which this follows so worktrees of the same repo collapse to
+/// one entry in the caller's `seen_git_dirs` set.
+fn resolve_git_dir(dot_git: &Path) -> PathBuf {
+    if dot_git.is_file() {
+        if let Ok(contents) = std::fs::read_to_string(dot_git) {
+            if let Some(pointee) = contents.trim().strip_prefix("gitdir:") {
+                let pointee = PathBuf::from(pointee.trim());
+                let resolved = if pointee.is_absolute() {
+                    pointee
+                } else {
+                    dot_git.parent().unwrap_or_else(|| Path::new(".")).join(pointee)
+                };
+                return resolved.canonicalize().unwrap_or(resolved);
+            }
+        }
+    }
+
+    dot_git.canonicalize().unwrap_or_else(|_| dot_git.to_path_buf())
+}
@@ -0,0 +1,371 @@
+// Semantic index over a repo's commit history - lets the observation generator
+// cite concrete commits instead of only aggregate pattern statistics. Commits are
+// chunked per touched file, embedded, and stored in a per-repo SQLite table keyed
+// by (sha, file_path); at analysis time a handful of "psychology probe" phrasings
+// (frustration, perfectionism, context-switching) are embedded and matched against
+// the stored vectors by cosine similarity to retrieve the most resonant commits.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::git_repository::{GitRepository, Libgit2GitRepository};
+
+/// How many of a repo's most recent commits are considered for embedding. Larger
+/// than `GitAnalyzer`'s 100-commit pattern window since chunking/embedding here is
+/// much cheaper per commit than the full behavioral analysis.
+const COMMIT_WINDOW: usize = 200;
+
+/// Phrasings representative of the psychological patterns this tool looks for,
+/// used as retrieval queries against the embedded commit history.
+pub const PSYCHOLOGY_PROBES: &[&str] = &[
+    "I'm so frustrated, this still doesn't work no matter what I try",
+    "rewriting this again to get it exactly right, still isn't good enough",
+    "quick context switch back to this after being away for a while, picking up where I left off",
+];
+
+/// What `EmbeddingIndex::nearest` retrieves for a probe query: the commit and file
+/// it's keyed by, plus the chunk text that was actually embedded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetrievedChunk {
+    pub sha: String,
+    pub file_path: String,
+    pub text: String,
+}
+
+/// Turns a chunk of text into a fixed-size vector. Kept as a trait so the
+/// deterministic local provider below can later be swapped for a real embedding
+/// model without touching `EmbeddingIndex` or the retrieval path.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    fn dimension(&self) -> usize;
+    async fn embed(&self, text: &str) -> Result<Vec<f32>>;
+}
+
+const LOCAL_EMBEDDING_DIMENSION: usize = 256;
+
+/// A hashed-shingle embedding: no network call or model weights required, so the
+/// semantic index works without any feature flag or API key. Deterministic and
+/// stable across runs - two runs of the same commit text always land in the same
+/// buckets, which is all cosine similarity needs to be meaningful here.
+pub struct LocalHashEmbeddingProvider;
+
+#[async_trait]
+impl EmbeddingProvider for LocalHashEmbeddingProvider {
+    fn dimension(&self) -> usize {
+        LOCAL_EMBEDDING_DIMENSION
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut vector = vec![0.0f32; LOCAL_EMBEDDING_DIMENSION];
+        for shingle in word_shingles(text, 3) {
+            let bucket = (hash_str(&shingle) as usize) % LOCAL_EMBEDDING_DIMENSION;
+            vector[bucket] += 1.0;
+        }
+        normalize(&mut vector);
+        Ok(vector)
+    }
+}
+
+fn word_shingles(text: &str, size: usize) -> Vec<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() < size {
+        return vec![text.to_lowercase()];
+    }
+    words.windows(size).map(|w| w.join(" ").to_lowercase()).collect()
+}
+
+fn hash_str(s: &str) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm: f32 = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= norm;
+        }
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 { 0.0 } else { dot / (norm_a * norm_b) }
+}
+
+/// Per-repo, on-disk store of chunk embeddings. One SQLite file per repo under
+/// `codex-d/embeddings/`, named from a hash of the repo's canonical path, so
+/// re-opening the same repo finds its index instantly instead of rebuilding it.
+pub struct EmbeddingIndex {
+    conn: Connection,
+}
+
+impl EmbeddingIndex {
+    pub fn open(repo_path: &str, dimension: usize) -> Result<Self> {
+        let db_path = Self::path_for(repo_path);
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create embedding index directory")?;
+        }
+
+        let conn = Connection::open(&db_path).context("Failed to open embedding index")?;
+        let index = Self { conn };
+        index.initialize_schema(dimension)?;
+        Ok(index)
+    }
+
+    fn path_for(repo_path: &str) -> PathBuf {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let canonical = std::fs::canonicalize(repo_path).unwrap_or_else(|_| PathBuf::from(repo_path));
+        let mut hasher = DefaultHasher::new();
+        canonical.hash(&mut hasher);
+
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("codex-d")
+            .join("embeddings")
+            .join(format!("{:016x}.db", hasher.finish()))
+    }
+
+    /// Creates the chunk table, pinning the embedding dimension on first use. If a
+    /// later model change produces a different dimension, the stored vectors are
+    /// incompatible with fresh queries, so the cache is dropped and rebuilt rather
+    /// than silently mixing embeddings from two different models.
+    fn initialize_schema(&self, dimension: usize) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS index_meta (key TEXT PRIMARY KEY, value TEXT NOT NULL)",
+            [],
+        ).context("Failed to create index_meta table")?;
+
+        let stored_dimension: Option<usize> = self.conn.query_row(
+            "SELECT value FROM index_meta WHERE key = 'dimension'",
+            [],
+            |row| row.get::<_, String>(0),
+        ).ok().and_then(|v| v.parse().ok());
+
+        if stored_dimension != Some(dimension) {
+            self.conn.execute("DROP TABLE IF EXISTS chunks", [])
+                .context("Failed to drop stale chunks table")?;
+            self.conn.execute(
+                "INSERT OR REPLACE INTO index_meta (key, value) VALUES ('dimension', ?1)",
+                params![dimension.to_string()],
+            ).context("Failed to record embedding dimension")?;
+        }
+
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS chunks (
+                sha TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                text TEXT NOT NULL,
+                embedding BLOB NOT NULL,
+                PRIMARY KEY (sha, file_path)
+            )
+            "#,
+            [],
+        ).context("Failed to create chunks table")?;
+
+        Ok(())
+    }
+
+    /// SHAs with at least one chunk already embedded, so the caller can skip
+    /// re-embedding them.
+    pub fn indexed_shas(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT DISTINCT sha FROM chunks")
+            .context("Failed to prepare indexed_shas query")?;
+        let shas = stmt.query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()
+            .context("Failed to read indexed shas")?;
+        Ok(shas)
+    }
+
+    /// Inserts or replaces a chunk's embedding, deduped on `(sha, file_path)`.
+    pub fn insert_chunk(&self, sha: &str, file_path: &str, text: &str, embedding: &[f32]) -> Result<()> {
+        let bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO chunks (sha, file_path, text, embedding) VALUES (?1, ?2, ?3, ?4)",
+            params![sha, file_path, text, bytes],
+        ).context("Failed to insert chunk embedding")?;
+        Ok(())
+    }
+
+    /// The `top_k` stored chunks most similar to `query_embedding`, highest
+    /// cosine-similarity first.
+    pub fn nearest(&self, query_embedding: &[f32], top_k: usize) -> Result<Vec<RetrievedChunk>> {
+        let mut stmt = self.conn.prepare("SELECT sha, file_path, text, embedding FROM chunks")
+            .context("Failed to prepare nearest-neighbor query")?;
+
+        let mut scored: Vec<(f32, RetrievedChunk)> = stmt.query_map([], |row| {
+            let sha: String = row.get(0)?;
+            let file_path: String = row.get(1)?;
+            let text: String = row.get(2)?;
+            let bytes: Vec<u8> = row.get(3)?;
+            Ok((sha, file_path, text, bytes))
+        })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to read chunks")?
+            .into_iter()
+            .map(|(sha, file_path, text, bytes)| {
+                let embedding: Vec<f32> = bytes.chunks_exact(4)
+                    .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect();
+                let score = cosine_similarity(query_embedding, &embedding);
+                (score, RetrievedChunk { sha, file_path, text })
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(top_k);
+        Ok(scored.into_iter().map(|(_, chunk)| chunk).collect())
+    }
+}
+
+/// Incrementally embeds `repo_path`'s recent commit history (skipping SHAs already
+/// in the index), streaming `(step, progress)` the same way `GitAnalyzer::analyze`
+/// does so page 2's checklist reflects real embedding work, then retrieves the
+/// top-k chunks per `PSYCHOLOGY_PROBES` query for the observation generator to cite.
+pub async fn index_and_retrieve(
+    repo_path: &str,
+    top_k: usize,
+    progress: impl Fn(String, f32) + Send + Sync + 'static,
+) -> Result<Vec<RetrievedChunk>> {
+    let provider = LocalHashEmbeddingProvider;
+    let index = EmbeddingIndex::open(repo_path, provider.dimension())?;
+
+    let repo = Libgit2GitRepository::open(repo_path)?;
+    let commits = repo.log(COMMIT_WINDOW)?;
+    let already_indexed = index.indexed_shas()?;
+
+    let to_embed: Vec<_> = commits.iter().filter(|c| !already_indexed.contains(&c.sha)).collect();
+    let total = to_embed.len();
+
+    if total == 0 {
+        progress("Commit embeddings up to date".to_string(), 100.0);
+    }
+
+    for (i, commit) in to_embed.iter().enumerate() {
+        let file_paths = if commit.files_changed.is_empty() {
+            vec![String::new()]
+        } else {
+            commit.files_changed.clone()
+        };
+
+        for file_path in file_paths {
+            let text = if file_path.is_empty() {
+                commit.message.clone()
+            } else {
+                format!("{}\n\nTouched: {}", commit.message, file_path)
+            };
+            let embedding = provider.embed(&text).await?;
+            index.insert_chunk(&commit.sha, &file_path, &text, &embedding)?;
+        }
+
+        progress(
+            format!("Embedding commit {}/{}", i + 1, total),
+            (i + 1) as f32 / total as f32 * 100.0,
+        );
+    }
+
+    let mut retrieved = Vec::new();
+    for probe in PSYCHOLOGY_PROBES {
+        let query_embedding = provider.embed(probe).await?;
+        retrieved.extend(index.nearest(&query_embedding, top_k)?);
+    }
+
+    retrieved.sort_by(|a, b| (a.sha.as_str(), a.file_path.as_str()).cmp(&(b.sha.as_str(), b.file_path.as_str())));
+    retrieved.dedup_by(|a, b| a.sha == b.sha && a.file_path == b.file_path);
+
+    Ok(retrieved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosine_similarity_of_identical_vectors_is_one() {
+        let v = vec![1.0, 2.0, 3.0];
+        assert!((cosine_similarity(&v, &v) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cosine_similarity_of_orthogonal_vectors_is_zero() {
+        let a = vec![1.0, 0.0];
+        let b = vec![0.0, 1.0];
+        assert!(cosine_similarity(&a, &b).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cosine_similarity_of_a_zero_vector_is_zero_not_nan() {
+        let a = vec![0.0, 0.0];
+        let b = vec![1.0, 1.0];
+        assert_eq!(cosine_similarity(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn normalize_produces_a_unit_vector() {
+        let mut v = vec![3.0, 4.0];
+        normalize(&mut v);
+        let norm: f32 = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn normalize_leaves_a_zero_vector_untouched() {
+        let mut v = vec![0.0, 0.0];
+        normalize(&mut v);
+        assert_eq!(v, vec![0.0, 0.0]);
+    }
+
+    #[tokio::test]
+    async fn nearest_ranks_the_more_similar_chunk_first() {
+        let conn = Connection::open_in_memory().unwrap();
+        let index = EmbeddingIndex { conn };
+        index.initialize_schema(LOCAL_EMBEDDING_DIMENSION).unwrap();
+
+        let provider = LocalHashEmbeddingProvider;
+        let about_rust = "fix: handle empty input in the rust parser";
+        let about_docs = "docs: fix typo in the README";
+
+        index.insert_chunk(
+            "sha1",
+            "src/parser.rs",
+            about_rust,
+            &provider.embed(about_rust).await.unwrap(),
+        ).unwrap();
+        index.insert_chunk(
+            "sha2",
+            "README.md",
+            about_docs,
+            &provider.embed(about_docs).await.unwrap(),
+        ).unwrap();
+
+        let query = provider.embed(about_rust).await.unwrap();
+        let results = index.nearest(&query, 2).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].sha, "sha1");
+    }
+
+    #[test]
+    fn indexed_shas_reflects_inserted_chunks() {
+        let conn = Connection::open_in_memory().unwrap();
+        let index = EmbeddingIndex { conn };
+        index.initialize_schema(LOCAL_EMBEDDING_DIMENSION).unwrap();
+
+        index.insert_chunk("sha1", "a.rs", "text", &vec![0.0; LOCAL_EMBEDDING_DIMENSION]).unwrap();
+
+        let shas = index.indexed_shas().unwrap();
+        assert!(shas.contains("sha1"));
+        assert_eq!(shas.len(), 1);
+    }
+}
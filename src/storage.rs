@@ -1,13 +1,260 @@
 // SQLite storage for conversation history and observation tracking
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use r2d2::{CustomizeConnection, Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{Connection, params};
 use std::path::PathBuf;
+use uuid::Uuid;
 
-use crate::types::{Message, GitAnalysis};
+use crate::types::{GitAnalysis, GitPattern, Message};
+
+/// One schema migration step, applied in order starting from whatever
+/// `PRAGMA user_version` already records on disk. Most steps are plain SQL;
+/// `Transform` exists for the rare migration that needs to read/rewrite row data
+/// (a backfill, a rename that Sqlite's `ALTER TABLE` can't express directly)
+/// rather than just altering the schema.
+enum Migration {
+    Sql(&'static str),
+    Transform(fn(&Connection) -> Result<()>),
+}
+
+/// v5's `Transform`: creates the FTS5 tables and sync triggers, then backfills
+/// them with every row the base tables already have, since the triggers only
+/// keep rows in sync from this point forward.
+fn migrate_v5_fts(conn: &Connection) -> Result<()> {
+    conn.execute_batch(r#"
+        CREATE VIRTUAL TABLE messages_fts USING fts5(
+            content,
+            content='messages',
+            content_rowid='id'
+        );
+
+        CREATE TRIGGER messages_fts_insert AFTER INSERT ON messages
+        BEGIN
+            INSERT INTO messages_fts(rowid, content) VALUES (NEW.id, NEW.content);
+        END;
+
+        CREATE TRIGGER messages_fts_delete AFTER DELETE ON messages
+        BEGIN
+            INSERT INTO messages_fts(messages_fts, rowid, content) VALUES ('delete', OLD.id, OLD.content);
+        END;
+
+        CREATE TRIGGER messages_fts_update AFTER UPDATE ON messages
+        BEGIN
+            INSERT INTO messages_fts(messages_fts, rowid, content) VALUES ('delete', OLD.id, OLD.content);
+            INSERT INTO messages_fts(rowid, content) VALUES (NEW.id, NEW.content);
+        END;
+
+        CREATE VIRTUAL TABLE observations_fts USING fts5(
+            observation,
+            patterns_summary,
+            content='observations',
+            content_rowid='id'
+        );
+
+        CREATE TRIGGER observations_fts_insert AFTER INSERT ON observations
+        BEGIN
+            INSERT INTO observations_fts(rowid, observation, patterns_summary)
+            VALUES (NEW.id, NEW.observation, NEW.patterns_summary);
+        END;
+
+        CREATE TRIGGER observations_fts_delete AFTER DELETE ON observations
+        BEGIN
+            INSERT INTO observations_fts(observations_fts, rowid, observation, patterns_summary)
+            VALUES ('delete', OLD.id, OLD.observation, OLD.patterns_summary);
+        END;
+
+        CREATE TRIGGER observations_fts_update AFTER UPDATE ON observations
+        BEGIN
+            INSERT INTO observations_fts(observations_fts, rowid, observation, patterns_summary)
+            VALUES ('delete', OLD.id, OLD.observation, OLD.patterns_summary);
+            INSERT INTO observations_fts(rowid, observation, patterns_summary)
+            VALUES (NEW.id, NEW.observation, NEW.patterns_summary);
+        END;
+    "#).context("Failed to create FTS5 tables and sync triggers")?;
+
+    conn.execute_batch(r#"
+        INSERT INTO messages_fts(rowid, content)
+            SELECT id, content FROM messages;
+
+        INSERT INTO observations_fts(rowid, observation, patterns_summary)
+            SELECT id, observation, patterns_summary FROM observations;
+    "#).context("Failed to backfill existing rows into FTS5 tables")?;
+
+    Ok(())
+}
+
+/// Schema versions in order - index 0 brings a fresh database to `user_version`
+/// 1, and so on. Append new versions here; never edit an already-released step,
+/// since a user's on-disk database may already be sitting at that version with
+/// the old step's effects applied.
+const MIGRATIONS: &[Migration] = &[
+    // v1: messages table, as originally shipped.
+    Migration::Sql(r#"
+        CREATE TABLE messages (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            role TEXT NOT NULL,
+            content TEXT NOT NULL,
+            timestamp INTEGER NOT NULL
+        )
+    "#),
+    // v2: observations table, for longitudinal tracking across sessions.
+    Migration::Sql(r#"
+        CREATE TABLE observations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            repo_path TEXT NOT NULL,
+            observation TEXT NOT NULL,
+            patterns_summary TEXT NOT NULL,
+            patterns_json TEXT NOT NULL DEFAULT '[]',
+            total_commits INTEGER NOT NULL,
+            severity REAL NOT NULL,
+            timestamp INTEGER NOT NULL
+        )
+    "#),
+    // v3: index for the "recent repos" / longitudinal-diff lookups.
+    Migration::Sql(r#"
+        CREATE INDEX idx_observations_repo ON observations(repo_path, timestamp DESC)
+    "#),
+    // v4: audit trail for edits and deletes, so `panic_wipe` and any future
+    // message-correction feature leave a recoverable trace instead of
+    // silently destroying the prior text.
+    Migration::Sql(r#"
+        CREATE TABLE messages_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            message_id INTEGER NOT NULL,
+            role TEXT NOT NULL,
+            content TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            action TEXT NOT NULL,
+            changed_at INTEGER NOT NULL
+        );
+
+        CREATE TRIGGER messages_history_update AFTER UPDATE ON messages
+        BEGIN
+            INSERT INTO messages_history (message_id, role, content, timestamp, action, changed_at)
+            VALUES (OLD.id, OLD.role, OLD.content, OLD.timestamp, 'update', strftime('%s', 'now'));
+        END;
+
+        CREATE TRIGGER messages_history_delete AFTER DELETE ON messages
+        BEGIN
+            INSERT INTO messages_history (message_id, role, content, timestamp, action, changed_at)
+            VALUES (OLD.id, OLD.role, OLD.content, OLD.timestamp, 'delete', strftime('%s', 'now'));
+        END;
+    "#),
+    // v5: full-text search over messages and observations, kept in sync with
+    // the base tables via the external-content-table trigger pattern, so
+    // `search_messages`/`search_observations` stay queryable as history grows
+    // instead of requiring a full `load_messages` scan. A `Transform` step
+    // since the triggers alone only cover rows inserted/updated/deleted after
+    // this migration runs - any row a database already had needs a one-time
+    // backfill into the new FTS tables too.
+    Migration::Transform(migrate_v5_fts),
+    // v6: append-only record log for cross-device sync. Records are addressed
+    // by a per-(host_id, tag) monotonically increasing `idx` rather than a
+    // parent pointer, since contiguous integers are trivial to diff (two sides
+    // just exchange "highest idx seen") and never need rewriting. `local_host`
+    // holds this machine's stable identity, generated once.
+    Migration::Sql(r#"
+        CREATE TABLE records (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            host_id TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            idx INTEGER NOT NULL,
+            payload TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            UNIQUE(host_id, tag, idx)
+        );
+
+        CREATE INDEX idx_records_host_tag_idx ON records(host_id, tag, idx);
+
+        CREATE TABLE local_host (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            host_id TEXT NOT NULL
+        );
+    "#),
+    // v7: normalized pattern rows alongside each observation, so recurring
+    // patterns can be aggregated by title/type with plain SQL instead of
+    // string-parsing the `patterns_summary` bullet list. `patterns_summary`
+    // and `patterns_json` are kept as-is for older rows and as a quick
+    // human-readable summary; this table is the queryable source of truth
+    // going forward.
+    Migration::Sql(r#"
+        CREATE TABLE observation_patterns (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            observation_id INTEGER NOT NULL REFERENCES observations(id) ON DELETE CASCADE,
+            pattern_type TEXT NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL,
+            severity REAL NOT NULL,
+            insight TEXT NOT NULL,
+            evidence_json TEXT NOT NULL DEFAULT '[]'
+        );
+
+        CREATE INDEX idx_observation_patterns_observation ON observation_patterns(observation_id);
+        CREATE INDEX idx_observation_patterns_title ON observation_patterns(title);
+    "#),
+];
+
+/// Brings `conn` up to `MIGRATIONS.len()`, running every step the on-disk
+/// `user_version` hasn't seen yet inside its own transaction before bumping the
+/// version, so a crash mid-migration leaves the database at a consistent
+/// version rather than partially upgraded. Refuses to run against a database
+/// newer than this binary knows about rather than risk corrupting its data.
+fn run_migrations(conn: &mut Connection) -> Result<()> {
+    let current_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+        .context("Failed to read schema version")?;
+    let current_version = current_version as usize;
+
+    if current_version > MIGRATIONS.len() {
+        bail!(
+            "Database schema version {} is newer than this binary supports (expected at most {}) - refusing to run against it",
+            current_version,
+            MIGRATIONS.len(),
+        );
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current_version) {
+        let new_version = index + 1;
+        let tx = conn.transaction().context("Failed to start migration transaction")?;
+
+        match migration {
+            Migration::Sql(sql) => {
+                tx.execute_batch(sql).with_context(|| format!("Migration {} failed", new_version))?;
+            }
+            Migration::Transform(transform) => {
+                transform(&tx).with_context(|| format!("Migration {} failed", new_version))?;
+            }
+        }
+
+        tx.pragma_update(None, "user_version", new_version as i64)
+            .with_context(|| format!("Failed to bump schema version to {}", new_version))?;
+        tx.commit().with_context(|| format!("Failed to commit migration {}", new_version))?;
+    }
+
+    Ok(())
+}
+
+/// Applied to every connection the pool hands out, so a reader checked out on
+/// one thread and a background observation writer checked out on another never
+/// trip `SQLITE_BUSY` against each other: WAL lets readers and a writer proceed
+/// concurrently, `busy_timeout` covers the brief window where a writer still
+/// holds the single write lock, and `foreign_keys` is opted in since SQLite
+/// leaves it off by default per-connection.
+#[derive(Debug)]
+struct ConnectionPragmas;
+
+impl CustomizeConnection<Connection, rusqlite::Error> for ConnectionPragmas {
+    fn on_acquire(&self, conn: &mut Connection) -> Result<(), rusqlite::Error> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "busy_timeout", 5_000)?;
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        Ok(())
+    }
+}
 
 pub struct Storage {
-    conn: Connection,
+    pool: Pool<SqliteConnectionManager>,
 }
 
 impl Storage {
@@ -21,57 +268,31 @@ impl Storage {
                 .context("Failed to create database directory")?;
         }
 
-        let conn = Connection::open(&path)
-            .context("Failed to open database")?;
+        // `:memory:` gives every new connection its own independent database, so
+        // a pool of them would each see a blank schema - cap the pool at one
+        // connection in that case so callers (and tests) always reuse the same
+        // in-memory database instead of a fresh one per checkout.
+        let max_size = if db_path == ":memory:" { 1 } else { 8 };
+
+        let manager = SqliteConnectionManager::file(&path);
+        let pool = Pool::builder()
+            .max_size(max_size)
+            .connection_customizer(Box::new(ConnectionPragmas))
+            .build(manager)
+            .context("Failed to build connection pool")?;
 
-        let storage = Self { conn };
-        storage.initialize_schema()?;
+        let mut conn = pool.get().context("Failed to check out a connection for migrations")?;
+        run_migrations(&mut conn)?;
+        drop(conn);
 
         eprintln!("Storage initialized at: {:?}", path);
 
-        Ok(storage)
+        Ok(Self { pool })
     }
 
-    fn initialize_schema(&self) -> Result<()> {
-        // Messages table
-        self.conn.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS messages (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                role TEXT NOT NULL,
-                content TEXT NOT NULL,
-                timestamp INTEGER NOT NULL
-            )
-            "#,
-            [],
-        ).context("Failed to create messages table")?;
-
-        // Observations table (for longitudinal tracking)
-        self.conn.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS observations (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                repo_path TEXT NOT NULL,
-                observation TEXT NOT NULL,
-                patterns_summary TEXT NOT NULL,
-                total_commits INTEGER NOT NULL,
-                severity REAL NOT NULL,
-                timestamp INTEGER NOT NULL
-            )
-            "#,
-            [],
-        ).context("Failed to create observations table")?;
-
-        // Index for efficient repo lookups
-        self.conn.execute(
-            r#"
-            CREATE INDEX IF NOT EXISTS idx_observations_repo
-            ON observations(repo_path, timestamp DESC)
-            "#,
-            [],
-        ).context("Failed to create observations index")?;
-
-        Ok(())
+    /// Checks out a pooled connection for one call's duration.
+    fn conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool.get().context("Failed to check out a pooled connection")
     }
 
     /// Save a message to the database
@@ -80,8 +301,9 @@ impl Storage {
             Message::User { content, timestamp } => ("user", content, timestamp),
             Message::Assistant { content, timestamp } => ("assistant", content, timestamp),
         };
+        let timestamp = crate::types::to_unix_seconds(*timestamp);
 
-        self.conn.execute(
+        self.conn()?.execute(
             "INSERT INTO messages (role, content, timestamp) VALUES (?1, ?2, ?3)",
             params![role, content, timestamp],
         ).context("Failed to save message")?;
@@ -91,14 +313,15 @@ impl Storage {
 
     /// Load all messages from the database
     pub fn load_messages(&self) -> Result<Vec<Message>> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
             "SELECT role, content, timestamp FROM messages ORDER BY timestamp ASC"
         ).context("Failed to prepare query")?;
 
         let messages = stmt.query_map([], |row| {
             let role: String = row.get(0)?;
             let content: String = row.get(1)?;
-            let timestamp: i64 = row.get(2)?;
+            let timestamp = crate::types::from_unix_seconds(row.get(2)?);
 
             Ok(match role.as_str() {
                 "user" => Message::User { content, timestamp },
@@ -113,11 +336,12 @@ impl Storage {
 
     /// Delete all messages (panic wipe)
     pub fn panic_wipe(&self) -> Result<()> {
-        self.conn.execute("DELETE FROM messages", [])
+        let conn = self.conn()?;
+        conn.execute("DELETE FROM messages", [])
             .context("Failed to delete messages")?;
 
         // Vacuum to reclaim space
-        self.conn.execute("VACUUM", [])
+        conn.execute("VACUUM", [])
             .context("Failed to vacuum database")?;
 
         eprintln!("Database wiped");
@@ -127,7 +351,7 @@ impl Storage {
 
     /// Get message count
     pub fn count_messages(&self) -> Result<usize> {
-        let count: i64 = self.conn.query_row(
+        let count: i64 = self.conn()?.query_row(
             "SELECT COUNT(*) FROM messages",
             [],
             |row| row.get(0)
@@ -147,31 +371,64 @@ impl Storage {
             .map(|p| format!("â€¢ {}: {}", p.title, p.description))
             .collect::<Vec<_>>()
             .join("\n");
+        let patterns_json = serde_json::to_string(&analysis.patterns)
+            .context("Failed to serialize patterns")?;
+
+        let mut conn = self.conn()?;
+        let tx = conn.transaction().context("Failed to start observation transaction")?;
 
-        self.conn.execute(
+        tx.execute(
             r#"
             INSERT INTO observations
-            (repo_path, observation, patterns_summary, total_commits, severity, timestamp)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            (repo_path, observation, patterns_summary, patterns_json, total_commits, severity, timestamp)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
             "#,
             params![
                 repo_path,
                 observation,
                 patterns_summary,
+                patterns_json,
                 analysis.total_commits_analyzed,
                 analysis.severity,
                 chrono::Utc::now().timestamp(),
             ],
         ).context("Failed to save observation")?;
 
+        let observation_id = tx.last_insert_rowid();
+
+        for pattern in &analysis.patterns {
+            let evidence_json = serde_json::to_string(&pattern.evidence)
+                .context("Failed to serialize pattern evidence")?;
+
+            tx.execute(
+                r#"
+                INSERT INTO observation_patterns
+                (observation_id, pattern_type, title, description, severity, insight, evidence_json)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                "#,
+                params![
+                    observation_id,
+                    pattern.pattern_type,
+                    pattern.title,
+                    pattern.description,
+                    pattern.severity,
+                    pattern.insight,
+                    evidence_json,
+                ],
+            ).context("Failed to save observation pattern")?;
+        }
+
+        tx.commit().context("Failed to commit observation")?;
+
         Ok(())
     }
 
     /// Load past observations for a repository (most recent first)
     pub fn load_observations(&self, repo_path: &str, limit: usize) -> Result<Vec<Observation>> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
             r#"
-            SELECT observation, patterns_summary, total_commits, severity, timestamp
+            SELECT id, observation, patterns_summary, patterns_json, total_commits, severity, timestamp
             FROM observations
             WHERE repo_path = ?1
             ORDER BY timestamp DESC
@@ -179,31 +436,456 @@ impl Storage {
             "#
         ).context("Failed to prepare observations query")?;
 
-        let observations = stmt.query_map(params![repo_path, limit], |row| {
-            Ok(Observation {
-                observation: row.get(0)?,
-                patterns_summary: row.get(1)?,
-                total_commits: row.get(2)?,
-                severity: row.get(3)?,
-                timestamp: row.get(4)?,
-            })
+        let rows = stmt.query_map(params![repo_path, limit], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, f64>(5)?,
+                row.get::<_, i64>(6)?,
+            ))
         })?.collect::<Result<Vec<_>, _>>()
             .context("Failed to parse observations")?;
 
+        let mut observations = Vec::with_capacity(rows.len());
+        for (id, observation, patterns_summary, patterns_json, total_commits, severity, timestamp) in rows {
+            let patterns = load_patterns_for_observation(&conn, id, &patterns_json)?;
+            observations.push(Observation {
+                id,
+                observation,
+                patterns_summary,
+                patterns_json,
+                patterns,
+                total_commits,
+                severity,
+                timestamp,
+            });
+        }
+
         Ok(observations)
     }
+
+    /// Aggregates recurring pattern titles for a repository across every
+    /// observation recorded for it, so a caller can surface "this pattern has
+    /// shown up N times" instead of string-parsing `patterns_summary`.
+    pub fn patterns_for_repo(&self, repo_path: &str) -> Result<Vec<RecurringPattern>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT op.title, op.pattern_type, COUNT(*) AS occurrences, MAX(op.severity) AS max_severity
+            FROM observation_patterns op
+            JOIN observations o ON o.id = op.observation_id
+            WHERE o.repo_path = ?1
+            GROUP BY op.title, op.pattern_type
+            ORDER BY occurrences DESC, max_severity DESC
+            "#
+        ).context("Failed to prepare patterns_for_repo query")?;
+
+        let patterns = stmt.query_map(params![repo_path], |row| {
+            Ok(RecurringPattern {
+                title: row.get(0)?,
+                pattern_type: row.get(1)?,
+                occurrences: row.get(2)?,
+                max_severity: row.get(3)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse recurring patterns")?;
+
+        Ok(patterns)
+    }
+
+    /// Load the most recent observation for a repository, with its patterns
+    /// deserialized, so a caller can diff the prior session's findings against
+    /// the current run. Returns `None` for a repo with no prior history.
+    pub fn load_latest_analysis(&self, repo_path: &str) -> Result<Option<Observation>> {
+        Ok(self.load_observations(repo_path, 1)?.into_iter().next())
+    }
+
+    /// Lists repositories with prior history, most recently analyzed first, for
+    /// the "recent repositories" shortcut on page 1.
+    pub fn list_recent_repos(&self, limit: usize) -> Result<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT repo_path
+            FROM observations
+            GROUP BY repo_path
+            ORDER BY MAX(timestamp) DESC
+            LIMIT ?1
+            "#
+        ).context("Failed to prepare recent repos query")?;
+
+        let repos = stmt.query_map(params![limit], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse recent repos")?;
+
+        Ok(repos)
+    }
+
+    /// Load the prior versions of a message recorded by the `messages_history`
+    /// triggers, most recent change first, so a caller can reconstruct what a
+    /// message said before an edit or a `panic_wipe` deleted it.
+    pub fn load_message_history(&self, message_id: i64) -> Result<Vec<MessageHistoryEntry>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT role, content, timestamp, action, changed_at
+            FROM messages_history
+            WHERE message_id = ?1
+            ORDER BY changed_at DESC, id DESC
+            "#
+        ).context("Failed to prepare message history query")?;
+
+        let history = stmt.query_map(params![message_id], |row| {
+            Ok(MessageHistoryEntry {
+                role: row.get(0)?,
+                content: row.get(1)?,
+                timestamp: row.get(2)?,
+                action: row.get(3)?,
+                changed_at: row.get(4)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse message history")?;
+
+        Ok(history)
+    }
+
+    /// Full-text search over message content, ranked by `bm25()` (closer to
+    /// zero is a better match) with the query terms highlighted in `snippet`.
+    pub fn search_messages(&self, query: &str, limit: usize) -> Result<Vec<MessageSearchHit>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT
+                m.id,
+                m.role,
+                m.content,
+                m.timestamp,
+                snippet(messages_fts, 0, '<<', '>>', '...', 10),
+                bm25(messages_fts) AS rank
+            FROM messages_fts
+            JOIN messages m ON m.id = messages_fts.rowid
+            WHERE messages_fts MATCH ?1
+            ORDER BY rank
+            LIMIT ?2
+            "#
+        ).context("Failed to prepare message search query")?;
+
+        let hits = stmt.query_map(params![query, limit], |row| {
+            Ok(MessageSearchHit {
+                id: row.get(0)?,
+                role: row.get(1)?,
+                content: row.get(2)?,
+                timestamp: row.get(3)?,
+                snippet: row.get(4)?,
+                rank: row.get(5)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse message search hits")?;
+
+        Ok(hits)
+    }
+
+    /// Full-text search over a repository's observations, ranked by `bm25()`
+    /// with the matched terms highlighted in `snippet`.
+    pub fn search_observations(
+        &self,
+        repo_path: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<ObservationSearchHit>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT
+                o.id,
+                o.observation,
+                o.timestamp,
+                snippet(observations_fts, 0, '<<', '>>', '...', 10),
+                bm25(observations_fts) AS rank
+            FROM observations_fts
+            JOIN observations o ON o.id = observations_fts.rowid
+            WHERE observations_fts MATCH ?2 AND o.repo_path = ?1
+            ORDER BY rank
+            LIMIT ?3
+            "#
+        ).context("Failed to prepare observation search query")?;
+
+        let hits = stmt.query_map(params![repo_path, query, limit], |row| {
+            Ok(ObservationSearchHit {
+                id: row.get(0)?,
+                observation: row.get(1)?,
+                timestamp: row.get(2)?,
+                snippet: row.get(3)?,
+                rank: row.get(4)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse observation search hits")?;
+
+        Ok(hits)
+    }
+
+    /// This machine's stable sync identity, generating and persisting one on
+    /// first use so it survives restarts.
+    pub fn host_id(&self) -> Result<String> {
+        let conn = self.conn()?;
+
+        // INSERT OR IGNORE then SELECT, rather than SELECT-then-INSERT, so
+        // two concurrent first-calls can't both miss the SELECT and then
+        // collide on the `id = 1` PRIMARY KEY - the loser of the insert race
+        // just falls through to reading back the winner's row.
+        conn.execute(
+            "INSERT OR IGNORE INTO local_host (id, host_id) VALUES (1, ?1)",
+            params![Uuid::new_v4().to_string()],
+        ).context("Failed to persist local host id")?;
+
+        conn.query_row(
+            "SELECT host_id FROM local_host WHERE id = 1",
+            [],
+            |row| row.get(0),
+        ).context("Failed to read local host id")
+    }
+
+    /// Appends an immutable record to this host's `idx` stream for `tag`,
+    /// returning the `idx` it was assigned. Deletions are modeled as records
+    /// too (e.g. a `"deleted"` payload), never by rewriting or removing a
+    /// previously written row.
+    pub fn append_record(&self, tag: &str, payload: &str) -> Result<i64> {
+        let host_id = self.host_id()?;
+        let conn = self.conn()?;
+
+        // Computing `next_idx` with a separate SELECT and then INSERTing it
+        // is a TOCTOU race between pooled connections appending to the same
+        // (host_id, tag) concurrently - two readers of the same MAX(idx)
+        // would then collide on the UNIQUE constraint. Folding the
+        // computation into the INSERT's own SELECT keeps the read and write
+        // in a single statement, so SQLite's single-writer lock covers both.
+        conn.query_row(
+            "INSERT INTO records (host_id, tag, idx, payload, created_at)
+             SELECT ?1, ?2, COALESCE(MAX(idx), -1) + 1, ?3, ?4
+             FROM records WHERE host_id = ?1 AND tag = ?2
+             RETURNING idx",
+            params![host_id, tag, payload, chrono::Utc::now().timestamp()],
+            |row| row.get(0),
+        ).context("Failed to append record")
+    }
+
+    /// The highest `idx` this database has stored for `(host_id, tag)`, or
+    /// `None` if it has never seen that stream - the starting point a peer
+    /// sends when asking "what's new since I last synced".
+    pub fn highest_idx(&self, host_id: &str, tag: &str) -> Result<Option<i64>> {
+        self.conn()?.query_row(
+            "SELECT MAX(idx) FROM records WHERE host_id = ?1 AND tag = ?2",
+            params![host_id, tag],
+            |row| row.get(0),
+        ).context("Failed to read highest record idx")
+    }
+
+    /// Records for `(host_id, tag)` with `idx` greater than `after_idx`, in
+    /// `idx` order - the transport-agnostic core of a sync push: a peer calls
+    /// this with the highest `idx` it already has and applies what comes back.
+    pub fn records_after(&self, host_id: &str, tag: &str, after_idx: i64) -> Result<Vec<Record>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT host_id, tag, idx, payload, created_at
+            FROM records
+            WHERE host_id = ?1 AND tag = ?2 AND idx > ?3
+            ORDER BY idx ASC
+            "#
+        ).context("Failed to prepare records_after query")?;
+
+        let records = stmt.query_map(params![host_id, tag, after_idx], |row| {
+            Ok(Record {
+                host_id: row.get(0)?,
+                tag: row.get(1)?,
+                idx: row.get(2)?,
+                payload: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse records")?;
+
+        Ok(records)
+    }
+
+    /// Writes a consistent point-in-time copy of the live database to
+    /// `dest_path`, using SQLite's online backup API so a long-running agent
+    /// can be snapshotted safely instead of risking a filesystem copy that
+    /// catches the main database mid-write or misses pages still sitting in
+    /// the WAL. If `dest_path` names an existing directory (or ends in a path
+    /// separator), the snapshot is auto-named by timestamp inside it;
+    /// otherwise `dest_path` is treated as the exact file to write. Returns
+    /// the path the snapshot was actually written to.
+    pub fn snapshot(&self, dest_path: &str) -> Result<PathBuf> {
+        let is_dir_target = dest_path.ends_with(std::path::MAIN_SEPARATOR)
+            || dest_path.ends_with('/')
+            || PathBuf::from(dest_path).is_dir();
+
+        let dest = if is_dir_target {
+            let dir = PathBuf::from(dest_path);
+            std::fs::create_dir_all(&dir).context("Failed to create snapshot directory")?;
+            dir.join(format!(
+                "codex-d-snapshot-{}.db",
+                chrono::Utc::now().format("%Y%m%dT%H%M%SZ"),
+            ))
+        } else {
+            let dest = PathBuf::from(dest_path);
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent).context("Failed to create snapshot directory")?;
+            }
+            dest
+        };
+
+        let src = self.conn()?;
+        let mut dst_conn = Connection::open(&dest)
+            .context("Failed to open snapshot destination")?;
+        let backup = rusqlite::backup::Backup::new(&src, &mut dst_conn)
+            .context("Failed to start online backup")?;
+        backup.run_to_completion(100, std::time::Duration::from_millis(50), None)
+            .context("Failed to complete online backup")?;
+
+        eprintln!("Snapshot written to: {:?}", dest);
+
+        Ok(dest)
+    }
+
+    /// Applies records received from a peer. Insertion is `INSERT OR IGNORE`
+    /// against the `(host_id, tag, idx)` UNIQUE constraint, so re-applying a
+    /// record already seen (e.g. after a retried sync) is a no-op rather than
+    /// an error. Returns how many records were newly inserted.
+    pub fn apply_records(&self, records: &[Record]) -> Result<usize> {
+        let conn = self.conn()?;
+        let mut applied = 0;
+
+        for record in records {
+            let inserted = conn.execute(
+                "INSERT OR IGNORE INTO records (host_id, tag, idx, payload, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![record.host_id, record.tag, record.idx, record.payload, record.created_at],
+            ).context("Failed to apply record")?;
+            applied += inserted;
+        }
+
+        Ok(applied)
+    }
+}
+
+/// A prior version of a message, recorded by the `messages_history` triggers
+/// just before an `UPDATE` or `DELETE` on `messages` overwrote or removed it.
+#[derive(Debug, Clone)]
+pub struct MessageHistoryEntry {
+    pub role: String,
+    pub content: String,
+    pub timestamp: i64,
+    pub action: String,
+    pub changed_at: i64,
+}
+
+/// One immutable entry in a host's `(tag, idx)` append-only record stream,
+/// as exchanged between devices during sync.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub host_id: String,
+    pub tag: String,
+    pub idx: i64,
+    pub payload: String,
+    pub created_at: i64,
+}
+
+/// One full-text search hit against `messages_fts`.
+#[derive(Debug, Clone)]
+pub struct MessageSearchHit {
+    pub id: i64,
+    pub role: String,
+    pub content: String,
+    pub timestamp: i64,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+/// One full-text search hit against `observations_fts`.
+#[derive(Debug, Clone)]
+pub struct ObservationSearchHit {
+    pub id: i64,
+    pub observation: String,
+    pub timestamp: i64,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+/// A pattern title's recurrence across every observation recorded for a
+/// repository, as aggregated by `patterns_for_repo`.
+#[derive(Debug, Clone)]
+pub struct RecurringPattern {
+    pub title: String,
+    pub pattern_type: String,
+    pub occurrences: i64,
+    pub max_severity: f64,
+}
+
+/// Reads the normalized rows from `observation_patterns` for one observation.
+/// Observations saved before that table existed have nothing there, so this
+/// falls back to deserializing the legacy `patterns_json` column instead.
+fn load_patterns_for_observation(
+    conn: &Connection,
+    observation_id: i64,
+    fallback_json: &str,
+) -> Result<Vec<GitPattern>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT pattern_type, title, description, severity, insight, evidence_json
+        FROM observation_patterns
+        WHERE observation_id = ?1
+        ORDER BY id ASC
+        "#
+    ).context("Failed to prepare observation patterns query")?;
+
+    let patterns = stmt.query_map(params![observation_id], |row| {
+        let evidence_json: String = row.get(5)?;
+        let evidence = serde_json::from_str(&evidence_json).unwrap_or_default();
+
+        Ok(GitPattern {
+            pattern_type: row.get(0)?,
+            title: row.get(1)?,
+            description: row.get(2)?,
+            evidence,
+            severity: row.get(3)?,
+            insight: row.get(4)?,
+        })
+    })?.collect::<Result<Vec<_>, _>>()
+        .context("Failed to parse observation patterns")?;
+
+    if !patterns.is_empty() {
+        return Ok(patterns);
+    }
+
+    Ok(serde_json::from_str(fallback_json).unwrap_or_default())
 }
 
 /// Historical observation for longitudinal tracking
 #[derive(Debug, Clone)]
 pub struct Observation {
+    pub id: i64,
     pub observation: String,
     pub patterns_summary: String,
+    pub patterns_json: String,
+    pub patterns: Vec<GitPattern>,
     pub total_commits: i64,
     pub severity: f64,
     pub timestamp: i64,
 }
 
+impl Observation {
+    /// The structured patterns behind this observation, rehydrated from the
+    /// normalized `observation_patterns` table (or the legacy `patterns_json`
+    /// column for older rows) at load time.
+    pub fn patterns(&self) -> Vec<GitPattern> {
+        self.patterns.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,4 +917,282 @@ mod tests {
         storage.panic_wipe().unwrap();
         assert_eq!(storage.count_messages().unwrap(), 0);
     }
+
+    #[test]
+    fn run_migrations_brings_a_fresh_database_to_the_latest_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn run_migrations_is_a_no_op_when_already_up_to_date() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+
+        // A second run must not try to re-apply already-applied steps (e.g.
+        // re-running a `CREATE TABLE` would error).
+        run_migrations(&mut conn).unwrap();
+
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn run_migrations_refuses_a_database_newer_than_this_binary_knows_about() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", (MIGRATIONS.len() + 1) as i64).unwrap();
+
+        assert!(run_migrations(&mut conn).is_err());
+    }
+
+    #[test]
+    fn pooled_connections_share_the_same_in_memory_database() {
+        // `:memory:` gives each physical connection its own database, so the
+        // pool is capped at one connection for that path - otherwise a second
+        // checkout would see a blank schema instead of the data the first one
+        // wrote.
+        let storage = Storage::new(":memory:").unwrap();
+
+        storage.save_message(&Message::user("from connection 1".to_string())).unwrap();
+        // A second, independent `conn()` checkout must still see the row
+        // above rather than a fresh in-memory database.
+        assert_eq!(storage.count_messages().unwrap(), 1);
+    }
+
+    #[test]
+    fn pooled_connections_enforce_foreign_keys() {
+        let storage = Storage::new(":memory:").unwrap();
+        let conn = storage.conn().unwrap();
+
+        let enabled: i64 = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+        assert_eq!(enabled, 1);
+    }
+
+    #[test]
+    fn deleting_a_message_records_its_prior_content_in_history() {
+        let storage = Storage::new(":memory:").unwrap();
+        storage.save_message(&Message::user("doomed message".to_string())).unwrap();
+
+        let conn = storage.conn().unwrap();
+        let message_id: i64 = conn.query_row("SELECT id FROM messages", [], |row| row.get(0)).unwrap();
+        conn.execute("DELETE FROM messages WHERE id = ?1", params![message_id]).unwrap();
+        drop(conn);
+
+        let history = storage.load_message_history(message_id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, "delete");
+        assert_eq!(history[0].content, "doomed message");
+    }
+
+    #[test]
+    fn updating_a_message_records_its_previous_content_in_history() {
+        let storage = Storage::new(":memory:").unwrap();
+        storage.save_message(&Message::user("original".to_string())).unwrap();
+
+        let conn = storage.conn().unwrap();
+        let message_id: i64 = conn.query_row("SELECT id FROM messages", [], |row| row.get(0)).unwrap();
+        conn.execute(
+            "UPDATE messages SET content = ?1 WHERE id = ?2",
+            params!["edited", message_id],
+        ).unwrap();
+        drop(conn);
+
+        let history = storage.load_message_history(message_id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, "update");
+        assert_eq!(history[0].content, "original");
+    }
+
+    #[test]
+    fn search_messages_finds_a_saved_message_by_content() {
+        let storage = Storage::new(":memory:").unwrap();
+        storage.save_message(&Message::user("the quick brown fox".to_string())).unwrap();
+        storage.save_message(&Message::assistant("nothing relevant here".to_string())).unwrap();
+
+        let hits = storage.search_messages("brown", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].content, "the quick brown fox");
+    }
+
+    #[test]
+    fn migrate_v5_fts_backfills_rows_that_predate_the_migration() {
+        // Apply only migrations v1-v4, insert a row the way an older database
+        // would already have one sitting on disk, then run the v5 `Transform`
+        // by hand - the FTS sync triggers alone would never see this row,
+        // since they only fire on writes that happen after they're created.
+        let mut conn = Connection::open_in_memory().unwrap();
+        for migration in &MIGRATIONS[..4] {
+            match migration {
+                Migration::Sql(sql) => conn.execute_batch(sql).unwrap(),
+                Migration::Transform(transform) => transform(&conn).unwrap(),
+            }
+        }
+
+        conn.execute(
+            "INSERT INTO messages (role, content, timestamp) VALUES ('user', 'pre-existing row', 0)",
+            [],
+        ).unwrap();
+
+        migrate_v5_fts(&conn).unwrap();
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM messages_fts WHERE messages_fts MATCH 'pre-existing'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn append_record_assigns_sequential_idx_starting_at_zero() {
+        let storage = Storage::new(":memory:").unwrap();
+        assert_eq!(storage.append_record("tag", "first").unwrap(), 0);
+        assert_eq!(storage.append_record("tag", "second").unwrap(), 1);
+        assert_eq!(storage.append_record("tag", "third").unwrap(), 2);
+    }
+
+    #[test]
+    fn append_record_keeps_separate_tags_independently_indexed() {
+        let storage = Storage::new(":memory:").unwrap();
+        assert_eq!(storage.append_record("a", "x").unwrap(), 0);
+        assert_eq!(storage.append_record("b", "y").unwrap(), 0);
+        assert_eq!(storage.append_record("a", "z").unwrap(), 1);
+    }
+
+    #[test]
+    fn concurrent_host_id_calls_never_collide_on_first_insert() {
+        use std::sync::Arc;
+
+        // `:memory:` forces the pool down to a single connection (see the
+        // sizing comment on `Storage::new`), so it can never hand out two
+        // connections at once and would pass this test even without the
+        // atomic-insert fix. Use a file-backed temp DB instead so the pool's
+        // default size lets 8 threads genuinely race on the same row.
+        let db_path = std::env::temp_dir().join(format!("codex-d-host-id-test-{}.db", Uuid::new_v4()));
+        let storage = Arc::new(Storage::new(db_path.to_str().unwrap()).unwrap());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let storage = Arc::clone(&storage);
+                std::thread::spawn(move || storage.host_id().unwrap())
+            })
+            .collect();
+
+        let ids: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        std::fs::remove_file(&db_path).ok();
+
+        assert!(ids.iter().all(|id| *id == ids[0]));
+    }
+
+    #[test]
+    fn concurrent_append_record_calls_never_collide_on_idx() {
+        use std::sync::Arc;
+
+        // See the comment in `concurrent_host_id_calls_never_collide_on_first_insert` -
+        // `:memory:` caps the pool at one connection, which can't exercise a real race.
+        let db_path = std::env::temp_dir().join(format!("codex-d-append-record-test-{}.db", Uuid::new_v4()));
+        let storage = Arc::new(Storage::new(db_path.to_str().unwrap()).unwrap());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let storage = Arc::clone(&storage);
+                std::thread::spawn(move || storage.append_record("sync", "payload").unwrap())
+            })
+            .collect();
+
+        let mut indices: Vec<i64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        std::fs::remove_file(&db_path).ok();
+
+        indices.sort_unstable();
+        assert_eq!(indices, (0..8).collect::<Vec<i64>>());
+    }
+
+    #[test]
+    fn snapshot_writes_an_independent_copy_with_the_same_data() {
+        let tmp_dir = std::env::temp_dir().join(format!("codex-d-storage-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+        let db_path = tmp_dir.join("main.db");
+
+        let storage = Storage::new(db_path.to_str().unwrap()).unwrap();
+        storage.save_message(&Message::user("snapshot me".to_string())).unwrap();
+
+        let dest_path = tmp_dir.join("snapshot.db");
+        let written = storage.snapshot(dest_path.to_str().unwrap()).unwrap();
+        assert_eq!(written, dest_path);
+
+        let snapshot_conn = Connection::open(&dest_path).unwrap();
+        let content: String = snapshot_conn
+            .query_row("SELECT content FROM messages", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(content, "snapshot me");
+
+        std::fs::remove_dir_all(&tmp_dir).ok();
+    }
+
+    #[test]
+    fn snapshot_auto_names_the_file_when_given_a_directory() {
+        let tmp_dir = std::env::temp_dir().join(format!("codex-d-storage-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+        let db_path = tmp_dir.join("main.db");
+
+        let storage = Storage::new(db_path.to_str().unwrap()).unwrap();
+
+        let snapshot_dir = tmp_dir.join("snapshots/");
+        let written = storage.snapshot(snapshot_dir.to_str().unwrap()).unwrap();
+        assert!(written.starts_with(&snapshot_dir));
+        assert!(written.exists());
+
+        std::fs::remove_dir_all(&tmp_dir).ok();
+    }
+
+    fn analysis_with_one_pattern(pattern_type: &str, title: &str, severity: f32) -> GitAnalysis {
+        GitAnalysis {
+            patterns: vec![GitPattern {
+                pattern_type: pattern_type.to_string(),
+                title: title.to_string(),
+                description: "description".to_string(),
+                evidence: vec![],
+                severity,
+                insight: "insight".to_string(),
+            }],
+            summary: "summary".to_string(),
+            total_commits_analyzed: 1,
+            severity,
+            time_investment: crate::types::TimeInvestment { total_hours: 0.0, by_author: vec![] },
+            commit_activity: vec![],
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn patterns_for_repo_aggregates_recurrences_across_observations() {
+        let storage = Storage::new(":memory:").unwrap();
+
+        storage
+            .save_observation("repo", "obs1", &analysis_with_one_pattern("self_merge_rate", "Self-Merge Rate", 0.5))
+            .unwrap();
+        storage
+            .save_observation("repo", "obs2", &analysis_with_one_pattern("self_merge_rate", "Self-Merge Rate", 0.75))
+            .unwrap();
+
+        let patterns = storage.patterns_for_repo("repo").unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].title, "Self-Merge Rate");
+        assert_eq!(patterns[0].occurrences, 2);
+        assert_eq!(patterns[0].max_severity, 0.75);
+    }
+
+    #[test]
+    fn load_observations_rehydrates_patterns_from_the_normalized_table() {
+        let storage = Storage::new(":memory:").unwrap();
+        storage
+            .save_observation("repo", "obs", &analysis_with_one_pattern("review_latency", "Review Latency", 0.3))
+            .unwrap();
+
+        let observations = storage.load_observations("repo", 1).unwrap();
+        assert_eq!(observations.len(), 1);
+        assert_eq!(observations[0].patterns().len(), 1);
+        assert_eq!(observations[0].patterns()[0].title, "Review Latency");
+    }
 }
@@ -0,0 +1,174 @@
+// Transport abstraction for the ACP JSON-RPC stream - decouples
+// `CodexAdapter` from any particular way of reaching `codex-acp`, so the
+// adapter's protocol logic (framing, request/response routing) works the same
+// whether the other end is a locally spawned child process or an
+// already-running service reachable over the network.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::{Child, ChildStdin, ChildStdout, Command};
+use std::sync::Mutex;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+/// One newline-delimited JSON-RPC line in, one line out, plus a way to tear
+/// the connection down. `CodexAdapter` speaks only in terms of this trait -
+/// `send_request`/`run_reader` don't know or care whether a line just
+/// travelled over a pipe or a socket.
+pub trait Transport: Send + Sync {
+    fn send_line(&self, line: &str) -> Result<()>;
+    fn read_line(&self) -> Result<String>;
+    fn shutdown(&self) -> Result<()>;
+}
+
+/// The original transport: `codex-acp` spawned as a local child process,
+/// talking JSON-RPC over its stdin/stdout. Used for both the plain local case
+/// and the SSH remote case (chunk7-2), since `ssh` itself is still a local
+/// child process from this machine's point of view.
+pub struct ChildProcessTransport {
+    child: Mutex<Child>,
+    stdin: Mutex<ChildStdin>,
+    stdout: Mutex<BufReader<ChildStdout>>,
+}
+
+impl ChildProcessTransport {
+    /// Spawns `command` (already configured with piped stdin/stdout by the
+    /// caller) and takes ownership of its handles.
+    pub fn spawn(command: &mut Command) -> Result<Self> {
+        let mut child = command.spawn().context("Failed to spawn transport child process")?;
+
+        let stdin = child.stdin.take()
+            .ok_or_else(|| anyhow!("Failed to get child stdin"))?;
+        let stdout = child.stdout.take()
+            .ok_or_else(|| anyhow!("Failed to get child stdout"))?;
+
+        Ok(Self {
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            stdout: Mutex::new(BufReader::new(stdout)),
+        })
+    }
+}
+
+impl Transport for ChildProcessTransport {
+    fn send_line(&self, line: &str) -> Result<()> {
+        let mut stdin = self.stdin.lock().unwrap();
+        writeln!(stdin, "{}", line)?;
+        stdin.flush()?;
+        Ok(())
+    }
+
+    fn read_line(&self) -> Result<String> {
+        let mut line = String::new();
+        let bytes_read = self.stdout.lock().unwrap().read_line(&mut line)?;
+        if bytes_read == 0 {
+            bail!("child process stdout closed (EOF)");
+        }
+        Ok(line)
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        let mut child = self.child.lock().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+        Ok(())
+    }
+}
+
+/// Either side of a `ws://`/`wss://` connection or a raw `tcp://` socket,
+/// reached at a configured URL instead of spawned - lets `codex-acp` run as a
+/// long-lived service shared across clients, or live inside a container, with
+/// the same JSON-RPC framing as the subprocess case.
+enum NetworkConnection {
+    WebSocket(Mutex<WebSocket<MaybeTlsStream<TcpStream>>>),
+    Tcp {
+        write_half: Mutex<TcpStream>,
+        read_half: Mutex<BufReader<TcpStream>>,
+    },
+}
+
+pub struct NetworkTransport {
+    connection: NetworkConnection,
+}
+
+impl NetworkTransport {
+    /// Connects to `url`, dispatching on its scheme: `ws://`/`wss://` go
+    /// through `tungstenite`'s blocking client, `tcp://` opens a raw
+    /// newline-delimited socket.
+    pub fn connect(url: &str) -> Result<Self> {
+        if url.starts_with("ws://") || url.starts_with("wss://") {
+            let (socket, _response) = tungstenite::connect(url)
+                .with_context(|| format!("Failed to connect to {}", url))?;
+            Ok(Self {
+                connection: NetworkConnection::WebSocket(Mutex::new(socket)),
+            })
+        } else if let Some(addr) = url.strip_prefix("tcp://") {
+            let stream = TcpStream::connect(addr)
+                .with_context(|| format!("Failed to connect to {}", url))?;
+            let read_half = stream.try_clone()
+                .context("Failed to clone TCP stream for reading")?;
+            Ok(Self {
+                connection: NetworkConnection::Tcp {
+                    write_half: Mutex::new(stream),
+                    read_half: Mutex::new(BufReader::new(read_half)),
+                },
+            })
+        } else {
+            bail!("Unsupported transport URL scheme (expected ws://, wss://, or tcp://): {}", url);
+        }
+    }
+}
+
+impl Transport for NetworkTransport {
+    fn send_line(&self, line: &str) -> Result<()> {
+        match &self.connection {
+            NetworkConnection::WebSocket(socket) => {
+                socket.lock().unwrap().send(Message::Text(line.to_string().into()))?;
+                Ok(())
+            }
+            NetworkConnection::Tcp { write_half, .. } => {
+                let mut stream = write_half.lock().unwrap();
+                writeln!(stream, "{}", line)?;
+                stream.flush()?;
+                Ok(())
+            }
+        }
+    }
+
+    fn read_line(&self) -> Result<String> {
+        match &self.connection {
+            NetworkConnection::WebSocket(socket) => {
+                loop {
+                    match socket.lock().unwrap().read()? {
+                        Message::Text(text) => return Ok(text.to_string()),
+                        Message::Close(_) => bail!("WebSocket connection closed"),
+                        // Ping/Pong/Binary frames carry no JSON-RPC line - keep
+                        // waiting for the next one.
+                        _ => continue,
+                    }
+                }
+            }
+            NetworkConnection::Tcp { read_half, .. } => {
+                let mut line = String::new();
+                let bytes_read = read_half.lock().unwrap().read_line(&mut line)?;
+                if bytes_read == 0 {
+                    bail!("TCP transport connection closed (EOF)");
+                }
+                Ok(line)
+            }
+        }
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        match &self.connection {
+            NetworkConnection::WebSocket(socket) => {
+                let _ = socket.lock().unwrap().close(None);
+            }
+            NetworkConnection::Tcp { write_half, .. } => {
+                let _ = write_half.lock().unwrap().shutdown(std::net::Shutdown::Both);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,335 @@
+// Headless terminal frontend - renders the same `CodexController` state the GPUI
+// `CodexView` does (timeline, lifecycle progress, streaming buffers) as scrollable
+// ratatui panels with a text input, so codex-d can run over SSH or in a headless
+// environment without a compositor. Mirrors git-next's experimental ratatui TUI:
+// one underlying state model, two frontends.
+
+use std::io;
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::execute;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Terminal;
+
+use crate::codex_adapter::CodexAdapter;
+use crate::controller::CodexController;
+use crate::forge::ForgeConfig;
+use crate::types::{self, now_timestamp, AppState, Message, TimelineEvent};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Runs the terminal frontend until the user quits (`Esc` or `Ctrl+C`), driving
+/// `controller` the same way `CodexView` does from GPUI, just polled synchronously
+/// from the render loop instead of via `cx.spawn`.
+pub fn run(mut controller: CodexController) -> Result<()> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut repo_input = String::new();
+    let mut message_input = String::new();
+
+    let result = (|| -> Result<()> {
+        loop {
+            terminal.draw(|frame| draw(frame, &controller, &repo_input, &message_input))?;
+
+            if !event::poll(POLL_INTERVAL)? {
+                continue;
+            }
+
+            let Event::Key(key) = event::read()? else { continue };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Esc => return Ok(()),
+                KeyCode::Enter => match controller.app_state {
+                    AppState::AwaitingRepoSelection => {
+                        if !repo_input.trim().is_empty() {
+                            select_repo(&mut controller, repo_input.clone());
+                            repo_input.clear();
+                        }
+                    }
+                    AppState::ChatActive => {
+                        if !message_input.trim().is_empty() {
+                            send_message(&mut controller, message_input.clone());
+                            message_input.clear();
+                        }
+                    }
+                    AppState::Enriching => {}
+                },
+                KeyCode::Backspace => match controller.app_state {
+                    AppState::AwaitingRepoSelection => { repo_input.pop(); }
+                    AppState::ChatActive => { message_input.pop(); }
+                    AppState::Enriching => {}
+                },
+                KeyCode::Char(c) => match controller.app_state {
+                    AppState::AwaitingRepoSelection => repo_input.push(c),
+                    AppState::ChatActive => message_input.push(c),
+                    AppState::Enriching => {}
+                },
+                _ => {}
+            }
+        }
+    })();
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    result
+}
+
+/// Runs git analysis (plus any forge metrics) synchronously on the calling thread
+/// and feeds the result straight into the controller - no progress channel, since
+/// the TUI has nothing better to do while it waits.
+fn select_repo(controller: &mut CodexController, repo_path: String) {
+    controller.begin_enrichment(repo_path.clone());
+
+    let forge_config = controller.forge_token.clone()
+        .and_then(|token| ForgeConfig::from_repo(&repo_path, token));
+
+    let analysis_result = controller.tokio_handle.clone().block_on(
+        CodexController::analyze_repo_with_forge(&repo_path, forge_config, |_step, _progress| {})
+    );
+
+    match analysis_result {
+        Ok(analysis) => {
+            controller.notify_webhook(&repo_path, &analysis);
+            let prior = controller.record_observation(&repo_path, &analysis);
+            let evidence = controller.tokio_handle.clone().block_on(
+                CodexController::gather_semantic_evidence(&repo_path, |_step, _progress| {})
+            );
+            let system_prompt = CodexController::build_system_prompt(&repo_path, &analysis, prior.as_ref(), &evidence);
+
+            match CodexAdapter::new().and_then(|adapter| {
+                adapter.spawn()?;
+                adapter.initialize()?;
+                let session_id = adapter.create_session(system_prompt, repo_path.clone())?;
+                Ok((adapter, session_id))
+            }) {
+                Ok((adapter, session_id)) => {
+                    eprintln!("Codex session created: {}", session_id);
+                    controller.codex_adapter = Some(std::sync::Arc::new(adapter));
+                    controller.lifecycle_events.push(types::LifecycleEvent::completed("Git analysis".to_string()));
+                    controller.lifecycle_events.push(types::LifecycleEvent::completed("AI initialized".to_string()));
+                    controller.app_state = AppState::ChatActive;
+                    controller.is_loading = false;
+
+                    let greeting = CodexController::discovery_greeting(&analysis);
+                    controller.timeline_events.push(TimelineEvent::AssistantMessage {
+                        content: greeting,
+                        timestamp: now_timestamp(),
+                    });
+                }
+                Err(e) => {
+                    controller.error_message = Some(format!("Failed to start Codex: {}", e));
+                    controller.app_state = AppState::AwaitingRepoSelection;
+                    controller.is_loading = false;
+                }
+            }
+        }
+        Err(e) => {
+            controller.error_message = Some(format!("Git analysis failed: {}", e));
+            controller.app_state = AppState::AwaitingRepoSelection;
+            controller.is_loading = false;
+        }
+    }
+}
+
+/// Sends a message and drains the streaming response synchronously - the TUI
+/// blocks on the reply the same way `select_repo` blocks on analysis, trading
+/// the GPUI frontend's incremental streaming for a simpler polling loop.
+fn send_message(controller: &mut CodexController, content: String) {
+    let now = now_timestamp();
+    controller.timeline_events.push(TimelineEvent::UserMessage { content: content.clone(), timestamp: now });
+
+    let message = Message::user(content.clone());
+    controller.messages.push(message.clone());
+    if let Some(storage) = &controller.storage {
+        let _ = storage.save_message(&message);
+    }
+
+    let Some(adapter) = controller.codex_adapter.clone() else {
+        controller.timeline_events.push(TimelineEvent::AssistantMessage {
+            content: "Error: Codex not initialized".to_string(),
+            timestamp: now_timestamp(),
+        });
+        return;
+    };
+
+    let (tx, rx) = smol::channel::bounded::<types::StreamEvent>(100);
+    let tokio_handle = controller.tokio_handle.clone();
+    let tx_clone = tx.clone();
+    let join = std::thread::spawn(move || {
+        tokio_handle.block_on(async move {
+            if let Err(e) = adapter.send_message(content, move |event| {
+                let _ = tx.send_blocking(event);
+            }) {
+                let _ = tx_clone.send_blocking(types::StreamEvent::MessageChunk(format!("\n\nError: {}", e)));
+            }
+        });
+    });
+
+    while let Ok(event) = rx.recv_blocking() {
+        match event {
+            types::StreamEvent::MessageChunk(chunk) => controller.current_message_buffer.push_str(&chunk),
+            types::StreamEvent::ThoughtChunk(chunk) => controller.current_thought_buffer.push_str(&chunk),
+            types::StreamEvent::ToolCall(tool_call) => {
+                controller.active_tool_calls.insert(tool_call.tool_call_id.clone(), (tool_call, String::new()));
+            }
+            types::StreamEvent::ToolCallUpdate(update) => {
+                if let Some((tool_call, output)) = controller.active_tool_calls.get_mut(&update.tool_call_id) {
+                    if let Some(status) = &update.status {
+                        tool_call.status = status.clone();
+                    }
+                    if let Some(content) = &update.content {
+                        output.push_str(content);
+                    }
+                }
+            }
+            types::StreamEvent::FileWrite(event) => {
+                eprintln!("Agent wrote {} bytes to {}", event.bytes_written, event.path);
+            }
+            types::StreamEvent::TerminalOutput(event) => {
+                eprintln!("[terminal {}] {}", event.terminal_id, event.output.trim_end());
+            }
+            types::StreamEvent::ConnectionReconnecting(event) => {
+                eprintln!("Reconnecting to codex-acp (attempt {}/{})", event.attempt, event.max_attempts);
+            }
+            types::StreamEvent::ConnectionReconnected => {
+                eprintln!("Reconnected to codex-acp");
+            }
+            types::StreamEvent::ConnectionFailed(reason) => {
+                eprintln!("codex-acp connection failed permanently: {}", reason);
+            }
+            _ => {}
+        }
+    }
+    let _ = join.join();
+
+    let now = now_timestamp();
+    if !controller.current_thought_buffer.is_empty() {
+        controller.timeline_events.push(TimelineEvent::Thought {
+            content: controller.current_thought_buffer.clone(),
+            timestamp: now,
+        });
+        controller.current_thought_buffer.clear();
+    }
+    for (_id, (tool_call, output)) in controller.active_tool_calls.drain() {
+        controller.timeline_events.push(TimelineEvent::ToolCall {
+            tool_call_id: tool_call.tool_call_id,
+            title: tool_call.title,
+            kind: tool_call.kind,
+            status: tool_call.status,
+            locations: tool_call.locations,
+            output: if output.is_empty() { None } else { Some(output) },
+            timestamp: now,
+            mcp_server: tool_call.mcp_server,
+            routed_via: None,
+        });
+    }
+    if !controller.current_message_buffer.is_empty() {
+        let msg_content = controller.current_message_buffer.clone();
+        controller.timeline_events.push(TimelineEvent::AssistantMessage { content: msg_content.clone(), timestamp: now });
+        if let Some(storage) = &controller.storage {
+            let _ = storage.save_message(&Message::assistant(msg_content));
+        }
+        controller.current_message_buffer.clear();
+    }
+}
+
+fn draw(frame: &mut ratatui::Frame, controller: &CodexController, repo_input: &str, message_input: &str) {
+    match controller.app_state {
+        AppState::AwaitingRepoSelection => draw_repo_selection(frame, controller, repo_input),
+        AppState::Enriching => draw_enriching(frame, controller),
+        AppState::ChatActive => draw_chat(frame, controller, message_input),
+    }
+}
+
+fn draw_repo_selection(frame: &mut ratatui::Frame, controller: &CodexController, repo_input: &str) {
+    let area = frame.area();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .split(area);
+
+    let input = Paragraph::new(repo_input)
+        .block(Block::default().borders(Borders::ALL).title("Repository path (Enter to analyze, Esc to quit)"));
+    frame.render_widget(input, chunks[0]);
+
+    let body = if let Some(error) = &controller.error_message {
+        Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red))
+    } else {
+        Paragraph::new("codex'd - type a path to a local git repository and press Enter.")
+    }
+    .wrap(Wrap { trim: true })
+    .block(Block::default().borders(Borders::ALL).title("codex'd"));
+    frame.render_widget(body, chunks[1]);
+}
+
+fn draw_enriching(frame: &mut ratatui::Frame, controller: &CodexController) {
+    let area = frame.area();
+    let lines: Vec<Line> = controller.lifecycle_events.iter().map(|event| {
+        let icon = match event.status {
+            types::LifecycleStatus::Pending => "⏳",
+            types::LifecycleStatus::Running => "🔄",
+            types::LifecycleStatus::Completed => "✅",
+            types::LifecycleStatus::Failed => "❌",
+        };
+        Line::from(format!("{} {}", icon, event.tool_name))
+    }).collect();
+
+    let title = format!("Analyzing {}", controller.selected_repo.as_deref().unwrap_or("repository"));
+    let body = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(body, area);
+}
+
+fn draw_chat(frame: &mut ratatui::Frame, controller: &CodexController, message_input: &str) {
+    let area = frame.area();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .split(area);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for event in &controller.timeline_events {
+        match event {
+            TimelineEvent::UserMessage { content, .. } => {
+                lines.push(Line::from(Span::styled(format!("you> {}", content), Style::default().fg(Color::Cyan))));
+            }
+            TimelineEvent::AssistantMessage { content, .. } => {
+                lines.push(Line::from(Span::styled(format!("codex'd> {}", content), Style::default().fg(Color::Green))));
+            }
+            TimelineEvent::Thought { content, .. } => {
+                lines.push(Line::from(Span::styled(format!("(thinking) {}", content), Style::default().fg(Color::DarkGray))));
+            }
+            TimelineEvent::ToolCall { title, status, .. } => {
+                lines.push(Line::from(format!("[tool] {} ({:?})", title, status)));
+            }
+        }
+    }
+    if !controller.current_thought_buffer.is_empty() {
+        lines.push(Line::from(Span::styled(format!("(thinking) {}", controller.current_thought_buffer), Style::default().fg(Color::DarkGray))));
+    }
+    if !controller.current_message_buffer.is_empty() {
+        lines.push(Line::from(Span::styled(format!("codex'd> {}", controller.current_message_buffer), Style::default().fg(Color::Green))));
+    }
+
+    let timeline = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title(controller.selected_repo.clone().unwrap_or_default()));
+    frame.render_widget(timeline, chunks[0]);
+
+    let input = Paragraph::new(message_input)
+        .block(Block::default().borders(Borders::ALL).title("Message (Enter to send, Esc to quit)"));
+    frame.render_widget(input, chunks[1]);
+}
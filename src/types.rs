@@ -1,6 +1,93 @@
 // Core data types for codex-d
 
+use codex_derive::Timestamped;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "chrono")]
+use chrono::TimeZone;
+
+// ============================================================================
+// Timestamps
+// ============================================================================
+
+/// The representation used for every event timestamp below. With the `chrono`
+/// feature off (the default), this is a raw unix-seconds `i64`, so downstream users
+/// aren't forced into the `chrono` dependency. With it on, timestamps are
+/// strongly-typed `DateTime<Utc>` — correct ordering and duration math, serialized as
+/// RFC3339 but still accepting a plain integer on the wire so old recordings made
+/// with the `i64` representation still deserialize.
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = i64;
+
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// "Now", in whichever representation `Timestamp` currently is.
+pub fn now_timestamp() -> Timestamp {
+    #[cfg(not(feature = "chrono"))]
+    {
+        chrono::Utc::now().timestamp()
+    }
+    #[cfg(feature = "chrono")]
+    {
+        chrono::Utc::now()
+    }
+}
+
+/// Converts a `Timestamp` to unix seconds regardless of which representation is
+/// active - useful for storage backends (SQLite columns, etc.) that always want a
+/// plain integer to index on.
+pub fn to_unix_seconds(ts: Timestamp) -> i64 {
+    #[cfg(not(feature = "chrono"))]
+    {
+        ts
+    }
+    #[cfg(feature = "chrono")]
+    {
+        ts.timestamp()
+    }
+}
+
+/// The inverse of `to_unix_seconds` - builds a `Timestamp` back up from unix seconds,
+/// e.g. when reloading a row a storage backend stored as a plain integer column.
+pub fn from_unix_seconds(secs: i64) -> Timestamp {
+    #[cfg(not(feature = "chrono"))]
+    {
+        secs
+    }
+    #[cfg(feature = "chrono")]
+    {
+        chrono::Utc.timestamp_opt(secs, 0).single().unwrap_or_else(chrono::Utc::now)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod timestamp_serde {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_rfc3339())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Wire {
+            UnixSeconds(i64),
+            Rfc3339(String),
+        }
+
+        match Wire::deserialize(deserializer)? {
+            Wire::UnixSeconds(secs) => Utc
+                .timestamp_opt(secs, 0)
+                .single()
+                .ok_or_else(|| D::Error::custom("out-of-range unix timestamp")),
+            Wire::Rfc3339(s) => DateTime::parse_from_rfc3339(&s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(D::Error::custom),
+        }
+    }
+}
 
 // ============================================================================
 // App State
@@ -25,13 +112,45 @@ pub enum StreamEvent {
     ToolCallUpdate(ToolCallUpdateEvent),
     LifecycleEvent(LifecycleEvent),
     PermissionRequest(PermissionRequest),
+    /// The agent wrote to a file via the `fs/write_text_file` capability.
+    FileWrite(FileWriteEvent),
+    /// Output arrived from a terminal the agent created via the `terminal/*`
+    /// capability.
+    TerminalOutput(TerminalOutputEvent),
+    /// The connection to codex-acp was lost and the supervisor is attempting
+    /// to reconnect and recreate the session.
+    ConnectionReconnecting(ReconnectEvent),
+    /// The supervisor successfully reconnected and recreated the session
+    /// after a connection loss.
+    ConnectionReconnected,
+    /// The supervisor exhausted its configured retry limit; the connection
+    /// is permanently lost.
+    ConnectionFailed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct FileWriteEvent {
+    pub path: String,
+    pub bytes_written: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct TerminalOutputEvent {
+    pub terminal_id: String,
+    pub output: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReconnectEvent {
+    pub attempt: u32,
+    pub max_attempts: u32,
 }
 
 // ============================================================================
 // MCP Server Types
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum McpServerType {
     #[serde(rename = "codex_psychology")]
     CodexPsychology,
@@ -61,6 +180,61 @@ impl McpServerType {
             Self::Gate22Gateway => "üåê",
         }
     }
+
+    /// Maps a registered `McpServerConfig`'s name to a known server type.
+    /// ACP's `tool_call` updates don't report which MCP server is handling a
+    /// tool, so this is a best-effort match against the names this adapter
+    /// has registered servers under.
+    pub fn from_server_name(name: &str) -> Option<Self> {
+        match name {
+            "codex-psychology" => Some(Self::CodexPsychology),
+            "aikido-scanner" => Some(Self::AikidoScanner),
+            "kontext-dev" => Some(Self::KontextDev),
+            "gate22-gateway" => Some(Self::Gate22Gateway),
+            _ => None,
+        }
+    }
+}
+
+/// How `create_session` reaches a registered MCP server once it's running.
+#[derive(Debug, Clone)]
+pub enum McpServerTransport {
+    Http {
+        url: String,
+        headers: Vec<(String, String)>,
+    },
+    Sse {
+        url: String,
+        headers: Vec<(String, String)>,
+    },
+    Stdio {
+        command: String,
+        args: Vec<String>,
+    },
+}
+
+/// Bootstraps a local MCP server process before a session connects to it,
+/// e.g. creating a Python venv and installing dependencies the first time.
+#[derive(Debug, Clone)]
+pub struct McpServerBootstrap {
+    pub working_dir: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub venv_setup: bool,
+}
+
+/// A runtime-registered MCP server, added via `CodexAdapter::add_mcp_server`
+/// and included in the `mcpServers` array of every session opened
+/// afterwards. Re-registering a server under a name that's already
+/// registered replaces its config.
+#[derive(Debug, Clone)]
+pub struct McpServerConfig {
+    pub name: String,
+    pub transport: McpServerTransport,
+    pub bootstrap: Option<McpServerBootstrap>,
+    /// How long to wait for the server to start accepting connections after
+    /// bootstrapping it, polled rather than a fixed sleep.
+    pub readiness_timeout: std::time::Duration,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,15 +297,17 @@ pub struct ToolCallUpdateEvent {
 // Messages
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Timestamped)]
 pub enum Message {
     User {
         content: String,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
     Assistant {
         content: String,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
 }
 
@@ -139,14 +315,14 @@ impl Message {
     pub fn user(content: String) -> Self {
         Self::User {
             content,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now_timestamp(),
         }
     }
 
     pub fn assistant(content: String) -> Self {
         Self::Assistant {
             content,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now_timestamp(),
         }
     }
 
@@ -157,13 +333,6 @@ impl Message {
         }
     }
 
-    pub fn timestamp(&self) -> i64 {
-        match self {
-            Self::User { timestamp, .. } => *timestamp,
-            Self::Assistant { timestamp, .. } => *timestamp,
-        }
-    }
-
     pub fn is_user(&self) -> bool {
         matches!(self, Self::User { .. })
     }
@@ -181,7 +350,8 @@ impl Message {
 pub struct LifecycleEvent {
     pub tool_name: String,
     pub status: LifecycleStatus,
-    pub timestamp: i64,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+    pub timestamp: Timestamp,
     pub error: Option<String>,
     pub progress: Option<f32>,  // 0-100 percentage for Running status
 }
@@ -199,7 +369,7 @@ impl LifecycleEvent {
         Self {
             tool_name,
             status: LifecycleStatus::Pending,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now_timestamp(),
             error: None,
             progress: None,
         }
@@ -209,7 +379,7 @@ impl LifecycleEvent {
         Self {
             tool_name,
             status: LifecycleStatus::Running,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now_timestamp(),
             error: None,
             progress: Some(0.0),
         }
@@ -219,7 +389,7 @@ impl LifecycleEvent {
         Self {
             tool_name,
             status: LifecycleStatus::Running,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now_timestamp(),
             error: None,
             progress: Some(progress.clamp(0.0, 100.0)),
         }
@@ -229,7 +399,7 @@ impl LifecycleEvent {
         Self {
             tool_name,
             status: LifecycleStatus::Completed,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now_timestamp(),
             error: None,
             progress: Some(100.0),
         }
@@ -239,7 +409,7 @@ impl LifecycleEvent {
         Self {
             tool_name,
             status: LifecycleStatus::Failed,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now_timestamp(),
             error: Some(error),
             progress: None,
         }
@@ -261,15 +431,44 @@ pub struct PermissionRequest {
 // Timeline Events (for chronological trajectory display)
 // ============================================================================
 
-#[derive(Debug, Clone)]
+/// A single external reference attached to a `SecurityFinding` (CWE, CVE,
+/// RUSTSEC advisory, ...). `kind` is the scanner's own label ("cwe", "cve",
+/// "rustsec", ...); the UI maps it onto a known identifier type when it can.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingIdentifier {
+    pub kind: String,
+    pub value: String,
+    pub url: Option<String>,
+}
+
+/// One occurrence of a `SecurityFinding` that recurs at more than one site -
+/// e.g. the same unsanitized-input pattern flagged in several files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingLocation {
+    pub file_path: String,
+    pub line_number: Option<u32>,
+}
+
+/// A titled external link attached to a `SecurityFinding` (writeup, changelog,
+/// upstream issue, ...), distinct from the structured `FindingIdentifier`
+/// database references.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingReference {
+    pub title: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Timestamped)]
 pub enum TimelineEvent {
     UserMessage {
         content: String,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
     Thought {
         content: String,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
     ToolCall {
         tool_call_id: String,
@@ -278,56 +477,64 @@ pub enum TimelineEvent {
         status: ToolCallStatus,
         locations: Vec<ToolCallLocation>,
         output: Option<String>,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
         mcp_server: Option<McpServerType>, // For transparency: which MCP server
         routed_via: Option<McpServerType>,  // If routed through gate22 gateway
     },
     AssistantMessage {
         content: String,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
     McpServerConnected {
         server_type: McpServerType,
         host: String,
         port: u16,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
     McpServerDisconnected {
         server_type: McpServerType,
         reason: Option<String>,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
     AgentFixPrompt {
         prompt: String,
         context: Option<String>,
-        timestamp: i64,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
     SecurityFinding {
         vulnerability_id: String,
         severity: String,  // "critical", "high", "medium", "low"
+        confidence: Option<String>,  // "confirmed", "high", "medium", "low"
         title: String,
         description: String,
         file_path: String,
         line_number: Option<u32>,
-        cwe_id: Option<String>,
+        /// External references (CWE/CVE/RUSTSEC/...) beyond `vulnerability_id`.
+        /// `url` is an explicit link from the scanner; the UI derives a
+        /// canonical lookup URL itself when this is `None`.
+        identifiers: Vec<FindingIdentifier>,
         recommendation: String,
-        timestamp: i64,
+        /// Other sites where the same finding recurs, beyond `file_path`/`line_number`.
+        #[serde(default)]
+        affected_instances: Vec<FindingLocation>,
+        /// Titled links (writeups, upstream issues, changelogs, ...).
+        #[serde(default)]
+        references: Vec<FindingReference>,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
+    },
+    CrashReport {
+        message: String,
+        demangled_frames: Vec<String>,
+        tool_name: Option<String>,
+        #[cfg_attr(feature = "chrono", serde(with = "timestamp_serde"))]
+        timestamp: Timestamp,
     },
-}
-
-impl TimelineEvent {
-    pub fn timestamp(&self) -> i64 {
-        match self {
-            Self::UserMessage { timestamp, .. } => *timestamp,
-            Self::Thought { timestamp, .. } => *timestamp,
-            Self::ToolCall { timestamp, .. } => *timestamp,
-            Self::AssistantMessage { timestamp, .. } => *timestamp,
-            Self::McpServerConnected { timestamp, .. } => *timestamp,
-            Self::McpServerDisconnected { timestamp, .. } => *timestamp,
-            Self::AgentFixPrompt { timestamp, .. } => *timestamp,
-            Self::SecurityFinding { timestamp, .. } => *timestamp,
-        }
-    }
 }
 
 // ============================================================================
@@ -340,6 +547,47 @@ pub struct GitAnalysis {
     pub summary: String,
     pub total_commits_analyzed: usize,
     pub severity: f32,
+    pub time_investment: TimeInvestment,
+    pub commit_activity: Vec<CommitActivity>,
+    pub warnings: Vec<GitWarning>,
+}
+
+/// A terse, discrete diagnostic flag surfaced while iterating over commits — unlike the
+/// reflective `insight` prose on `GitPattern`, these are meant to be listed compactly for
+/// quick triage rather than scored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitWarning {
+    pub kind: GitWarningKind,
+    pub message: String,
+    pub commit_shas: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum GitWarningKind {
+    QuickRevert,
+    DuplicateMessageBurst,
+    MassiveFileTouch,
+    LongCommitGap,
+}
+
+/// A single commit's timestamp, kept around purely for activity visualizations
+/// (heatmaps, punch cards) rather than behavioral scoring.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitActivity {
+    pub timestamp: i64,
+}
+
+/// Estimated hours spent on the repository, using the git-hours session-gap heuristic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeInvestment {
+    pub total_hours: f32,
+    pub by_author: Vec<AuthorHours>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorHours {
+    pub author: String,
+    pub hours: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -358,3 +606,23 @@ pub struct CommitEvidence {
     pub message: String,
     pub lines_changed: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unix_seconds_roundtrip_through_timestamp() {
+        let secs = 1_700_000_000_i64;
+        let ts = from_unix_seconds(secs);
+        assert_eq!(to_unix_seconds(ts), secs);
+    }
+
+    #[test]
+    fn now_timestamp_converts_to_a_plausible_unix_time() {
+        // Sanity bound rather than an exact value, since `now_timestamp` is
+        // wall-clock - just confirm it round-trips to something after this
+        // file was written, not a default/zeroed value.
+        assert!(to_unix_seconds(now_timestamp()) > 1_700_000_000);
+    }
+}
@@ -0,0 +1,82 @@
+// Collapsible wrapper around `render_markdown` that hides oversized content
+// behind a "Show full output (N tokens)" toggle, per `truncate::TruncateConfig`.
+use gpui::*;
+use gpui::prelude::*;
+
+use super::markdown::render_markdown;
+use super::theme::active_theme;
+use super::truncate::{truncate_content, TruncateConfig, TruncateDirection};
+
+pub struct CollapsibleText {
+    content: SharedString,
+    direction: TruncateDirection,
+    token_budget: usize,
+    expanded: bool,
+}
+
+impl CollapsibleText {
+    pub fn new(content: impl Into<SharedString>, direction: TruncateDirection, token_budget: usize) -> Self {
+        Self {
+            content: content.into(),
+            direction,
+            token_budget,
+            expanded: false,
+        }
+    }
+}
+
+impl Render for CollapsibleText {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = active_theme(cx);
+        let result = truncate_content(
+            &self.content,
+            &TruncateConfig {
+                direction: self.direction,
+                token_budget: self.token_budget,
+            },
+        );
+
+        let shown: SharedString = if self.expanded || !result.truncated {
+            self.content.clone()
+        } else {
+            result.visible.clone().into()
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(render_markdown(&shown))
+            .when(result.truncated, |container| {
+                let label = if self.expanded {
+                    "Show less".to_string()
+                } else {
+                    format!("Show full output ({} tokens)", result.total_tokens)
+                };
+                container.child(
+                    div()
+                        .id("collapsible-toggle")
+                        .text_xs()
+                        .text_color(theme.link_accent)
+                        .cursor_pointer()
+                        .hover(|style| style.text_color(theme.link_accent_hover))
+                        .on_click(cx.listener(|this, _event, _window, cx| {
+                            this.expanded = !this.expanded;
+                            cx.notify();
+                        }))
+                        .child(label)
+                )
+            })
+    }
+}
+
+/// Spawns a fresh [`CollapsibleText`] entity rendering `content`, collapsing it
+/// behind a toggle once it exceeds `token_budget` estimated tokens.
+pub fn collapsible_text(
+    cx: &mut App,
+    content: impl Into<SharedString>,
+    direction: TruncateDirection,
+    token_budget: usize,
+) -> Entity<CollapsibleText> {
+    cx.new(|_cx| CollapsibleText::new(content, direction, token_budget))
+}
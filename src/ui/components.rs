@@ -3,44 +3,67 @@ use gpui::*;
 use gpui::prelude::*;
 use gpui_component::v_flex;
 
-use crate::types::{TimelineEvent, ToolCallStatus, ToolCallEvent, McpServerType};
+use crate::types::{FindingIdentifier, FindingLocation, FindingReference, TimelineEvent, ToolCallStatus, ToolCallEvent, McpServerType};
+use super::markdown::render_markdown_streaming;
+use super::copy_button::copy_button;
+use super::collapsible::collapsible_text;
+use super::truncate::TruncateDirection;
+use super::theme::{active_theme, Theme};
+use super::security_finding::{
+    security_finding_card, Confidence, FileLocation, Identifier, IdentifierKind, Reference, SecurityFindingData,
+    Severity,
+};
+
+/// Default token budget before an assistant message or thought collapses
+/// behind a "Show full output" toggle.
+const MESSAGE_TOKEN_BUDGET: usize = 800;
+/// Default token budget before tool output collapses. Kept smaller than
+/// `MESSAGE_TOKEN_BUDGET` since tool dumps (logs, file contents) balloon fast.
+const TOOL_OUTPUT_TOKEN_BUDGET: usize = 400;
 
 // ============================================================================
 // Timeline Item Renderer
 // ============================================================================
 
-pub fn render_timeline_event(event: &TimelineEvent) -> Div {
+pub fn render_timeline_event(event: &TimelineEvent, cx: &mut App) -> Div {
     match event {
-        TimelineEvent::UserMessage { content, .. } => render_user_message(content),
-        TimelineEvent::Thought { content, .. } => render_thought(content),
+        TimelineEvent::UserMessage { content, .. } => render_user_message(content, cx),
+        TimelineEvent::Thought { content, .. } => render_thought(content, cx),
         TimelineEvent::ToolCall { title, kind, status, locations, output, mcp_server, routed_via, .. } => {
-            render_tool_call(title, kind, status, locations, output.as_deref(), mcp_server.as_ref(), routed_via.as_ref())
+            render_tool_call(title, kind, status, locations, output.as_deref(), mcp_server.as_ref(), routed_via.as_ref(), cx)
         }
-        TimelineEvent::AssistantMessage { content, .. } => render_assistant_message(content),
+        TimelineEvent::AssistantMessage { content, .. } => render_assistant_message(content, cx),
         TimelineEvent::McpServerConnected { server_type, host, port, .. } => {
-            render_mcp_server_connected(server_type, host, *port)
+            render_mcp_server_connected(server_type, host, *port, cx)
         }
         TimelineEvent::McpServerDisconnected { server_type, reason, .. } => {
-            render_mcp_server_disconnected(server_type, reason.as_deref())
+            render_mcp_server_disconnected(server_type, reason.as_deref(), cx)
         }
         TimelineEvent::AgentFixPrompt { prompt, .. } => {
-            render_agent_fix_prompt(prompt)
+            render_agent_fix_prompt(prompt, cx)
         }
         TimelineEvent::SecurityFinding {
-            vulnerability_id, severity, title, description,
-            file_path, line_number, cwe_id, recommendation, ..
+            vulnerability_id, severity, confidence, title, description,
+            file_path, line_number, identifiers, recommendation, affected_instances, references, ..
         } => {
             render_security_finding(
                 vulnerability_id,
                 severity,
+                confidence.as_deref(),
                 title,
                 description,
                 file_path,
                 *line_number,
-                cwe_id.as_deref(),
-                recommendation
+                identifiers,
+                recommendation,
+                affected_instances,
+                references,
+                cx,
             )
         }
+        TimelineEvent::CrashReport { message, demangled_frames, tool_name, .. } => {
+            render_crash_report(message, demangled_frames, tool_name.as_deref(), cx)
+        }
     }
 }
 
@@ -48,7 +71,9 @@ pub fn render_timeline_event(event: &TimelineEvent) -> Div {
 // Message Components
 // ============================================================================
 
-pub fn render_user_message(content: &str) -> Div {
+pub fn render_user_message(content: &str, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
+
     div()
         .flex()
         .w_full()
@@ -60,22 +85,22 @@ pub fn render_user_message(content: &str) -> Div {
                 .max_w(rems(40.0))  // Changed from px(600) to rems for better responsiveness
                 .px_3()
                 .py_2()
-                .bg(rgb(0xe8f2ff))
+                .bg(theme.user_bubble_bg)
                 .border_1()
-                .border_color(rgb(0x90caf9))
+                .border_color(theme.user_bubble_border)
                 .rounded_md()
                 .gap_1()
                 .child(
                     div()
                         .text_xs()
                         .font_weight(FontWeight::BOLD)
-                        .text_color(rgb(0x1976d2))
+                        .text_color(theme.user_accent)
                         .child("👤 You")
                 )
                 .child(
                     div()
                         .text_sm()
-                        .text_color(rgb(0x212121))
+                        .text_color(theme.primary_text)
                         .line_height(relative(1.5))
                         .overflow_x_hidden()  // Prevent horizontal overflow
                         .child(content.to_string())
@@ -83,7 +108,9 @@ pub fn render_user_message(content: &str) -> Div {
         )
 }
 
-pub fn render_assistant_message(content: &str) -> Div {
+pub fn render_assistant_message(content: &str, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
+
     div()
         .flex()
         .w_full()
@@ -95,25 +122,22 @@ pub fn render_assistant_message(content: &str) -> Div {
                 .max_w(rems(40.0))  // Changed from px(600) to rems for better responsiveness
                 .px_3()
                 .py_2()
-                .bg(rgb(0xf0f4f8))
+                .bg(theme.assistant_bubble_bg)
                 .border_1()
-                .border_color(rgb(0xcfd8dc))
+                .border_color(theme.assistant_bubble_border)
                 .rounded_md()
                 .gap_1()
                 .child(
                     div()
                         .text_xs()
                         .font_weight(FontWeight::BOLD)
-                        .text_color(rgb(0x546e7a))
+                        .text_color(theme.assistant_accent)
                         .child("🤖 Assistant")
                 )
                 .child(
                     div()
-                        .text_sm()
-                        .text_color(rgb(0x212121))
-                        .line_height(relative(1.5))
                         .overflow_x_hidden()  // Prevent horizontal overflow
-                        .child(content.to_string())
+                        .child(collapsible_text(cx, content.to_string(), TruncateDirection::End, MESSAGE_TOKEN_BUDGET))
                 )
         )
 }
@@ -122,7 +146,9 @@ pub fn render_assistant_message(content: &str) -> Div {
 // Thought Component (Perplexity-style thinking indicator)
 // ============================================================================
 
-pub fn render_thought(content: &str) -> Div {
+pub fn render_thought(content: &str, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
+
     div()
         .flex()
         .w_full()
@@ -134,25 +160,22 @@ pub fn render_thought(content: &str) -> Div {
                 .max_w(rems(40.0))  // Changed from px(600) to rems for better responsiveness
                 .px_3()
                 .py_2()
-                .bg(rgb(0xfff8e1))
+                .bg(theme.thought_bubble_bg)
                 .border_1()
-                .border_color(rgb(0xffd54f))
+                .border_color(theme.thought_bubble_border)
                 .rounded_md()
                 .gap_1()
                 .child(
                     div()
                         .text_xs()
                         .font_weight(FontWeight::BOLD)
-                        .text_color(rgb(0xf57c00))
+                        .text_color(theme.thought_accent)
                         .child("💭 Thinking...")
                 )
                 .child(
                     div()
-                        .text_sm()
-                        .text_color(rgb(0x5d4037))
-                        .line_height(relative(1.5))
                         .overflow_x_hidden()  // Prevent horizontal overflow
-                        .child(content.to_string())
+                        .child(collapsible_text(cx, content.to_string(), TruncateDirection::End, MESSAGE_TOKEN_BUDGET))
                 )
         )
 }
@@ -161,11 +184,11 @@ pub fn render_thought(content: &str) -> Div {
 // Tool Call Component (Step-by-step trajectory item)
 // ============================================================================
 
-fn get_status_text(status: &ToolCallStatus) -> (&'static str, Rgba) {
+fn get_status_text(status: &ToolCallStatus, theme: &Theme) -> (&'static str, Rgba) {
     match status {
-        ToolCallStatus::InProgress => ("🔄 Running", rgb(0x1976d2)),
-        ToolCallStatus::Completed => ("✅ Completed", rgb(0x388e3c)),
-        ToolCallStatus::Failed => ("❌ Failed", rgb(0xd32f2f)),
+        ToolCallStatus::InProgress => ("🔄 Running", theme.status_running),
+        ToolCallStatus::Completed => ("✅ Completed", theme.status_completed),
+        ToolCallStatus::Failed => ("❌ Failed", theme.status_failed),
     }
 }
 
@@ -177,9 +200,10 @@ fn render_tool_call(
     output: Option<&str>,
     mcp_server: Option<&McpServerType>,
     routed_via: Option<&McpServerType>,
+    cx: &mut App,
 ) -> Div {
-    let bg_tool_call = rgb(0xe8f5e9);
-    let (status_text, status_color) = get_status_text(status);
+    let theme = active_theme(cx);
+    let (status_text, status_color) = get_status_text(status, &theme);
 
     div()
         .flex()
@@ -191,9 +215,9 @@ fn render_tool_call(
                 .max_w(px(600.0))
                 .px_3()
                 .py_1p5()
-                .bg(bg_tool_call)
+                .bg(theme.tool_bg)
                 .border_1()
-                .border_color(rgb(0x81c784))
+                .border_color(theme.tool_border)
                 .rounded_md()
                 .flex()
                 .flex_col()
@@ -208,7 +232,7 @@ fn render_tool_call(
                             div()
                                 .text_sm()
                                 .font_weight(FontWeight::BOLD)
-                                .text_color(rgb(0x2e7d32))
+                                .text_color(theme.tool_accent)
                                 .child(format!("🔧 {}", title))
                         )
                         .child(
@@ -228,7 +252,7 @@ fn render_tool_call(
                             .child(
                                 div()
                                     .text_xs()
-                                    .text_color(rgb(0x616161))
+                                    .text_color(theme.mono_text)
                                     .child(format!("{} via {}",
                                         server.icon(),
                                         server.display_name()
@@ -238,7 +262,7 @@ fn render_tool_call(
                                 div_inner.child(
                                     div()
                                         .text_xs()
-                                        .text_color(rgb(0x757575))
+                                        .text_color(theme.muted_text)
                                         .child(format!("→ {} {}",
                                             gateway.icon(),
                                             gateway.display_name()
@@ -252,7 +276,7 @@ fn render_tool_call(
                     container.child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x616161))
+                            .text_color(theme.mono_text)
                             .child(
                                 locations
                                     .iter()
@@ -266,11 +290,20 @@ fn render_tool_call(
                 .when_some(output, |container, out| {
                     container.child(
                         div()
-                            .text_xs()
-                            .text_color(rgb(0x424242))
-                            .font_family("monospace")
-                            .line_height(relative(1.5))
-                            .child(out.to_string())
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .flex()
+                                    .justify_end()
+                                    .child(copy_button(cx, out.to_string(), "📋 Copy output"))
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .child(collapsible_text(cx, out.to_string(), TruncateDirection::Middle, TOOL_OUTPUT_TOKEN_BUDGET))
+                            )
                     )
                 })
         )
@@ -281,8 +314,8 @@ fn render_tool_call(
 // ============================================================================
 
 /// Renders a streaming thought (while it's being received)
-pub fn render_streaming_thought(content: &str) -> Div {
-    let bg_thought = rgb(0xfff8e1);
+pub fn render_streaming_thought(content: &str, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
 
     div()
         .flex()
@@ -295,9 +328,9 @@ pub fn render_streaming_thought(content: &str) -> Div {
                 .max_w(rems(40.0))  // Changed from px(600) to rems for better responsiveness
                 .px_3()
                 .py_2()
-                .bg(bg_thought)
+                .bg(theme.thought_bubble_bg)
                 .border_1()
-                .border_color(rgb(0xffd54f))
+                .border_color(theme.thought_bubble_border)
                 .rounded_lg()
                 .flex()
                 .flex_col()
@@ -306,24 +339,20 @@ pub fn render_streaming_thought(content: &str) -> Div {
                     div()
                         .text_xs()
                         .font_weight(FontWeight::BOLD)
-                        .text_color(rgb(0xf57c00))
+                        .text_color(theme.thought_accent)
                         .child("💭 Thinking...")
                 )
                 .child(
                     div()
-                        .text_sm()
-                        .text_color(rgb(0x5d4037))
-                        .line_height(relative(1.5))
                         .overflow_x_hidden()  // Prevent horizontal overflow
-                        .child(format!("{}▊", content)) // Cursor animation
+                        .child(render_markdown_streaming(content))
                 )
         )
 }
 
 /// Renders a streaming assistant message (while it's being received)
-pub fn render_streaming_message(content: &str) -> Div {
-    let bg_assistant = rgb(0xf0f4f8);
-    let border_assistant = rgb(0xcfd8dc);
+pub fn render_streaming_message(content: &str, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
 
     div()
         .flex()
@@ -336,9 +365,9 @@ pub fn render_streaming_message(content: &str) -> Div {
                 .max_w(rems(40.0))  // Changed from px(600) to rems for better responsiveness
                 .px_3()
                 .py_2()
-                .bg(bg_assistant)
+                .bg(theme.assistant_bubble_bg)
                 .border_1()
-                .border_color(border_assistant)
+                .border_color(theme.assistant_bubble_border)
                 .rounded_lg()
                 .flex()
                 .flex_col()
@@ -347,24 +376,21 @@ pub fn render_streaming_message(content: &str) -> Div {
                     div()
                         .text_xs()
                         .font_weight(FontWeight::BOLD)
-                        .text_color(rgb(0x546e7a))
+                        .text_color(theme.assistant_accent)
                         .child("🤖 Assistant")
                 )
                 .child(
                     div()
-                        .text_sm()
-                        .text_color(rgb(0x212121))
-                        .line_height(relative(1.5))
                         .overflow_x_hidden()  // Prevent horizontal overflow
-                        .child(format!("{}▊", content)) // Cursor animation
+                        .child(render_markdown_streaming(content))
                 )
         )
 }
 
 /// Renders an active tool call (while it's running)
-pub fn render_streaming_tool_call(tool_call: &ToolCallEvent, output: &str) -> Div {
-    let bg_tool_call = rgb(0xe8f5e9);
-    let (status_text, status_color) = get_status_text(&tool_call.status);
+pub fn render_streaming_tool_call(tool_call: &ToolCallEvent, output: &str, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
+    let (status_text, status_color) = get_status_text(&tool_call.status, &theme);
 
     div()
         .flex()
@@ -377,9 +403,9 @@ pub fn render_streaming_tool_call(tool_call: &ToolCallEvent, output: &str) -> Di
                 .max_w(rems(40.0))  // Changed from px(600) to rems for better responsiveness
                 .px_3()
                 .py_2()
-                .bg(bg_tool_call)
+                .bg(theme.tool_bg)
                 .border_1()
-                .border_color(rgb(0x81c784))
+                .border_color(theme.tool_border)
                 .rounded_lg()
                 .flex()
                 .flex_col()
@@ -393,7 +419,7 @@ pub fn render_streaming_tool_call(tool_call: &ToolCallEvent, output: &str) -> Di
                             div()
                                 .text_sm()
                                 .font_weight(FontWeight::BOLD)
-                                .text_color(rgb(0x2e7d32))
+                                .text_color(theme.tool_accent)
                                 .child(format!("🔧 {}", tool_call.title))
                         )
                         .child(
@@ -407,7 +433,7 @@ pub fn render_streaming_tool_call(tool_call: &ToolCallEvent, output: &str) -> Di
                     container.child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x616161))
+                            .text_color(theme.mono_text)
                             .child(
                                 tool_call.locations
                                     .iter()
@@ -421,11 +447,8 @@ pub fn render_streaming_tool_call(tool_call: &ToolCallEvent, output: &str) -> Di
                     container.child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x424242))
-                            .font_family("monospace")
-                            .line_height(relative(1.5))
                             .overflow_x_hidden()  // Prevent horizontal overflow
-                            .child(format!("{}▊", output)) // Cursor animation
+                            .child(collapsible_text(cx, output.to_string(), TruncateDirection::Middle, TOOL_OUTPUT_TOKEN_BUDGET))
                     )
                 })
         )
@@ -435,9 +458,8 @@ pub fn render_streaming_tool_call(tool_call: &ToolCallEvent, output: &str) -> Di
 // MCP Server Connection Components (for transparency)
 // ============================================================================
 
-pub fn render_mcp_server_connected(server_type: &McpServerType, host: &str, port: u16) -> Div {
-    let bg_mcp = rgb(0xe3f2fd);
-    let border_mcp = rgb(0x90caf9);
+pub fn render_mcp_server_connected(server_type: &McpServerType, host: &str, port: u16, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
 
     div()
         .flex()
@@ -449,9 +471,9 @@ pub fn render_mcp_server_connected(server_type: &McpServerType, host: &str, port
                 .max_w(px(600.0))
                 .px_3()
                 .py_1p5()
-                .bg(bg_mcp)
+                .bg(theme.mcp_connected_bg)
                 .border_1()
-                .border_color(border_mcp)
+                .border_color(theme.mcp_connected_border)
                 .rounded_md()
                 .flex()
                 .items_center()
@@ -460,13 +482,13 @@ pub fn render_mcp_server_connected(server_type: &McpServerType, host: &str, port
                     div()
                         .text_xs()
                         .font_weight(FontWeight::BOLD)
-                        .text_color(rgb(0x1976d2))
+                        .text_color(theme.user_accent)
                         .child("✅ Connected")
                 )
                 .child(
                     div()
                         .text_xs()
-                        .text_color(rgb(0x424242))
+                        .text_color(theme.body_text)
                         .child(format!("{} {} at {}:{}",
                             server_type.icon(),
                             server_type.display_name(),
@@ -477,9 +499,8 @@ pub fn render_mcp_server_connected(server_type: &McpServerType, host: &str, port
         )
 }
 
-pub fn render_mcp_server_disconnected(server_type: &McpServerType, reason: Option<&str>) -> Div {
-    let bg_mcp = rgb(0xfce4ec);
-    let border_mcp = rgb(0xf48fb1);
+pub fn render_mcp_server_disconnected(server_type: &McpServerType, reason: Option<&str>, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
 
     div()
         .flex()
@@ -492,9 +513,9 @@ pub fn render_mcp_server_disconnected(server_type: &McpServerType, reason: Optio
                 .max_w(rems(40.0))  // Changed from px(600) to rems for better responsiveness
                 .px_3()
                 .py_2()
-                .bg(bg_mcp)
+                .bg(theme.mcp_disconnected_bg)
                 .border_1()
-                .border_color(border_mcp)
+                .border_color(theme.mcp_disconnected_border)
                 .rounded_lg()
                 .flex()
                 .flex_col()
@@ -508,13 +529,13 @@ pub fn render_mcp_server_disconnected(server_type: &McpServerType, reason: Optio
                             div()
                                 .text_xs()
                                 .font_weight(FontWeight::BOLD)
-                                .text_color(rgb(0xc2185b))
+                                .text_color(theme.mcp_disconnected_accent)
                                 .child("⚠️ Disconnected")
                         )
                         .child(
                             div()
                                 .text_xs()
-                                .text_color(rgb(0x424242))
+                                .text_color(theme.body_text)
                                 .child(format!("{} {}",
                                     server_type.icon(),
                                     server_type.display_name()
@@ -525,7 +546,7 @@ pub fn render_mcp_server_disconnected(server_type: &McpServerType, reason: Optio
                     container.child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x616161))
+                            .text_color(theme.mono_text)
                             .child(format!("Reason: {}", r))
                     )
                 })
@@ -536,9 +557,8 @@ pub fn render_mcp_server_disconnected(server_type: &McpServerType, reason: Optio
 // Agent Fix Prompt Component (with copy functionality)
 // ============================================================================
 
-pub fn render_agent_fix_prompt(prompt: &str) -> Div {
-    let bg_prompt = rgb(0xfff9c4);
-    let border_prompt = rgb(0xfff176);
+pub fn render_agent_fix_prompt(prompt: &str, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
 
     div()
         .flex()
@@ -551,9 +571,9 @@ pub fn render_agent_fix_prompt(prompt: &str) -> Div {
                 .max_w(rems(40.0))  // Changed from px(600) to rems for better responsiveness
                 .px_3()
                 .py_2()
-                .bg(bg_prompt)
+                .bg(theme.agent_prompt_bg)
                 .border_1()
-                .border_color(border_prompt)
+                .border_color(theme.agent_prompt_border)
                 .rounded_lg()
                 .flex()
                 .flex_col()
@@ -567,26 +587,21 @@ pub fn render_agent_fix_prompt(prompt: &str) -> Div {
                             div()
                                 .text_xs()
                                 .font_weight(FontWeight::BOLD)
-                                .text_color(rgb(0xf57f17))
+                                .text_color(theme.agent_prompt_accent)
                                 .child("🤖 Agent Fix Prompt")
                         )
-                        .child(
-                            div()
-                                .text_xs()
-                                .text_color(rgb(0x616161))
-                                .child("📋 Hover to copy")
-                        )
+                        .child(copy_button(cx, prompt.to_string(), "📋 Hover to copy"))
                 )
                 .child(
                     div()
                         .px_3()
                         .py_2()
-                        .bg(rgb(0xfffde7))
+                        .bg(theme.agent_prompt_code_bg)
                         .border_1()
-                        .border_color(rgb(0xfbc02d))
+                        .border_color(theme.agent_prompt_code_border)
                         .rounded(px(4.0))
                         .text_xs()
-                        .text_color(rgb(0x424242))
+                        .text_color(theme.body_text)
                         .font_family("monospace")
                         .line_height(relative(1.5))
                         .overflow_x_hidden()  // Prevent horizontal overflow
@@ -595,7 +610,7 @@ pub fn render_agent_fix_prompt(prompt: &str) -> Div {
                 .child(
                     div()
                         .text_xs()
-                        .text_color(rgb(0x757575))
+                        .text_color(theme.muted_text)
                         .child("💡 Copy this prompt and feed it to a sub-agent for auto-fixing")
                 )
         )
@@ -605,25 +620,60 @@ pub fn render_agent_fix_prompt(prompt: &str) -> Div {
 // Security Finding Component (Aikido scan results)
 // ============================================================================
 
+/// Renders a single finding inline in the timeline (e.g. a scan emitting
+/// findings one-by-one as it runs). Backed by the same `SecurityFindingCard`
+/// entity `ui::security_panel::SecurityFindingsPanel` uses for batched,
+/// filterable triage of a full scan's results - starts expanded here since
+/// there's only ever one card to look at.
 pub fn render_security_finding(
     vulnerability_id: &str,
     severity: &str,
+    confidence: Option<&str>,
     title: &str,
     description: &str,
     file_path: &str,
     line_number: Option<u32>,
-    cwe_id: Option<&str>,
+    identifiers: &[FindingIdentifier],
     recommendation: &str,
+    affected_instances: &[FindingLocation],
+    references: &[FindingReference],
+    cx: &mut App,
 ) -> Div {
-    // Severity-based styling
-    let (bg_color, border_color, icon) = match severity.to_lowercase().as_str() {
-        "critical" => (rgb(0xffebee), rgb(0xef5350), "🚨"),
-        "high" => (rgb(0xfff3e0), rgb(0xfb8c00), "⚠️"),
-        "medium" => (rgb(0xfff9c4), rgb(0xfdd835), "⚡"),
-        "low" => (rgb(0xe8f5e9), rgb(0x66bb6a), "ℹ️"),
-        _ => (rgb(0xf5f5f5), rgb(0x9e9e9e), "📋"),
+    let data = SecurityFindingData {
+        vulnerability_id: vulnerability_id.to_string(),
+        severity: Severity::parse(severity),
+        confidence: confidence.map(Confidence::parse),
+        title: title.to_string(),
+        description: description.to_string(),
+        file_path: file_path.to_string(),
+        line_number,
+        identifiers: identifiers
+            .iter()
+            .map(|identifier| Identifier::new(IdentifierKind::parse(&identifier.kind), identifier.value.clone(), identifier.url.clone()))
+            .collect(),
+        recommendation: recommendation.to_string(),
+        affected_instances: affected_instances
+            .iter()
+            .map(|instance| FileLocation { file_path: instance.file_path.clone(), line_number: instance.line_number })
+            .collect(),
+        references: references
+            .iter()
+            .map(|reference| Reference { title: reference.title.clone(), url: reference.url.clone() })
+            .collect(),
     };
 
+    div()
+        .flex()
+        .w_full()
+        .px_2()
+        .py_2()
+        .justify_start()
+        .child(security_finding_card(cx, data, true))
+}
+
+pub fn render_crash_report(message: &str, demangled_frames: &[String], tool_name: Option<&str>, cx: &mut App) -> Div {
+    let theme = active_theme(cx);
+
     div()
         .flex()
         .w_full()
@@ -635,118 +685,50 @@ pub fn render_security_finding(
                 .max_w(rems(40.0))
                 .px_3()
                 .py_2()
-                .bg(bg_color)
+                .bg(theme.crash_bg)
                 .border_1()
-                .border_color(border_color)
-                .rounded_md()
+                .border_color(theme.crash_border)
+                .rounded_lg()
                 .flex()
                 .flex_col()
-                .gap_2()
-                .child(
-                    // Header: Icon + Severity + Title
-                    div()
-                        .flex()
-                        .items_center()
-                        .gap_2()
-                        .child(
-                            div()
-                                .text_lg()
-                                .child(icon)
-                        )
-                        .child(
-                            div()
-                                .flex()
-                                .flex_col()
-                                .gap_0p5()
-                                .child(
-                                    div()
-                                        .text_xs()
-                                        .font_weight(FontWeight::BOLD)
-                                        .text_color(border_color)
-                                        .child(severity.to_uppercase())
-                                )
-                                .child(
-                                    div()
-                                        .text_sm()
-                                        .font_weight(FontWeight::SEMIBOLD)
-                                        .text_color(rgb(0x212121))
-                                        .child(title.to_string())
-                                )
-                        )
-                )
+                .gap_1()
                 .child(
-                    // File location
                     div()
-                        .flex()
-                        .items_center()
-                        .gap_1()
-                        .child(
-                            div()
-                                .text_xs()
-                                .text_color(rgb(0x616161))
-                                .child("📄")
-                        )
-                        .child(
-                            div()
-                                .text_xs()
-                                .text_color(rgb(0x616161))
-                                .font_family("monospace")
-                                .child(
-                                    if let Some(line) = line_number {
-                                        format!("{}:{}", file_path, line)
-                                    } else {
-                                        file_path.to_string()
-                                    }
-                                )
-                        )
+                        .text_xs()
+                        .font_weight(FontWeight::BOLD)
+                        .text_color(theme.crash_accent)
+                        .child("💥 Crash Report")
                 )
-                .when_some(cwe_id, |container, cwe| {
+                .when_some(tool_name, |container, tool_name| {
                     container.child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x757575))
-                            .child(format!("CWE: {}", cwe))
+                            .text_color(theme.mono_text)
+                            .child(format!("in {}", tool_name))
                     )
                 })
                 .child(
-                    // Description
                     div()
                         .text_sm()
-                        .text_color(rgb(0x424242))
+                        .text_color(theme.body_text)
                         .line_height(relative(1.5))
-                        .child(description.to_string())
-                )
-                .child(
-                    // Recommendation section
-                    div()
-                        .mt_2()
-                        .pt_2()
-                        .border_t_1()
-                        .border_color(border_color)
-                        .flex()
-                        .flex_col()
-                        .gap_1()
-                        .child(
-                            div()
-                                .text_xs()
-                                .font_weight(FontWeight::BOLD)
-                                .text_color(rgb(0x616161))
-                                .child("💡 Recommendation")
-                        )
-                        .child(
-                            div()
-                                .text_xs()
-                                .text_color(rgb(0x424242))
-                                .line_height(relative(1.5))
-                                .child(recommendation.to_string())
-                        )
+                        .child(message.to_string())
                 )
-                .when(!vulnerability_id.is_empty(), |container| {
+                .when(!demangled_frames.is_empty(), |container| {
                     container.child(
                         div()
+                            .px_3()
+                            .py_2()
+                            .bg(theme.crash_code_bg)
+                            .border_1()
+                            .border_color(theme.crash_border)
+                            .rounded(px(4.0))
                             .text_xs()
-                            .text_color(rgb(0x9e9e9e))
-                            .child(format!("ID: {}", vulnerability_id))
+                            .text_color(theme.body_text)
+                            .font_family("monospace")
+                            .line_height(relative(1.5))
+                            .overflow_x_hidden()
+                            .children(demangled_frames.iter().map(|frame| div().child(frame.clone())))
                     )
                 })
         )
@@ -0,0 +1,76 @@
+// Reusable "copy to clipboard" affordance used anywhere a timeline item offers
+// text a user might want to paste elsewhere (agent fix prompts, tool output, ...).
+use gpui::*;
+use gpui::prelude::*;
+use std::time::Duration;
+
+use super::theme::active_theme;
+
+const FLASH_DURATION: Duration = Duration::from_secs(2);
+
+/// A small clickable label that copies `text` to the system clipboard and flashes
+/// "✅ Copied!" for a couple of seconds before reverting to its resting `label`.
+pub struct CopyButton {
+    text: SharedString,
+    label: SharedString,
+    copied: bool,
+}
+
+impl CopyButton {
+    pub fn new(text: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self {
+            text: text.into(),
+            label: label.into(),
+            copied: false,
+        }
+    }
+}
+
+impl Render for CopyButton {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = active_theme(cx);
+        let text = self.text.clone();
+        let display = if self.copied {
+            SharedString::from("✅ Copied!")
+        } else {
+            self.label.clone()
+        };
+
+        div()
+            .id("copy-button")
+            .text_xs()
+            .text_color(if self.copied {
+                theme.copy_success_accent
+            } else {
+                theme.mono_text
+            })
+            .cursor_pointer()
+            .hover(|style| style.text_color(theme.link_accent))
+            .on_click(cx.listener(move |this, _event, window, cx| {
+                window.write_to_clipboard(ClipboardItem::new_string(text.to_string()));
+                this.copied = true;
+                cx.notify();
+
+                cx.spawn(async move |this, cx| {
+                    cx.background_executor().timer(FLASH_DURATION).await;
+                    this.update(cx, |this, cx| {
+                        this.copied = false;
+                        cx.notify();
+                    })
+                    .ok();
+                })
+                .detach();
+            }))
+            .child(display)
+    }
+}
+
+/// Spawns a fresh [`CopyButton`] entity for `text`, resting on `label` until clicked.
+/// Each call site gets its own entity, so callers don't need to stash one themselves.
+pub fn copy_button(
+    cx: &mut App,
+    text: impl Into<SharedString>,
+    label: impl Into<SharedString>,
+) -> Entity<CopyButton> {
+    cx.new(|_cx| CopyButton::new(text, label))
+}
@@ -0,0 +1,498 @@
+// Markdown rendering for assistant messages, thoughts, and tool output - splits
+// content into block elements (paragraphs, headings, lists, blockquotes, fenced code)
+// and renders fenced code with tree-sitter-based syntax highlighting instead of
+// dumping everything into one flat, unstyled `div()`.
+
+use gpui::*;
+use gpui::prelude::*;
+
+use tree_sitter_highlight::{Highlighter, HighlightConfiguration, HighlightEvent};
+
+// ============================================================================
+// Block / inline model
+// ============================================================================
+
+#[derive(Debug, Clone)]
+enum Block {
+    Heading(u8, Vec<InlineSpan>),
+    Paragraph(Vec<InlineSpan>),
+    BulletList(Vec<Vec<InlineSpan>>),
+    NumberedList(Vec<Vec<InlineSpan>>),
+    Blockquote(Vec<InlineSpan>),
+    CodeBlock { language: Option<String>, lines: Vec<Vec<HighlightedRun>> },
+}
+
+#[derive(Debug, Clone)]
+struct InlineSpan {
+    text: String,
+    bold: bool,
+    italic: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+/// A single styled run within one line of a highlighted code block.
+#[derive(Debug, Clone)]
+struct HighlightedRun {
+    text: String,
+    color: Rgba,
+}
+
+// ============================================================================
+// Block parsing
+// ============================================================================
+
+fn parse_blocks(content: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut lines = content.lines().peekable();
+    let mut paragraph_buf: Vec<&str> = Vec::new();
+
+    fn flush_paragraph(buf: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+        if !buf.is_empty() {
+            blocks.push(Block::Paragraph(parse_inline(&buf.join(" "))));
+            buf.clear();
+        }
+    }
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_end();
+
+        // Fenced code block - ``` or ```lang. An unterminated fence (no closing ```
+        // before EOF) is treated as "the rest of the stream is code", which is both
+        // the documented edge case and what naturally happens mid-stream.
+        if let Some(rest) = trimmed.trim_start().strip_prefix("```") {
+            flush_paragraph(&mut paragraph_buf, &mut blocks);
+            let language = {
+                let tag = rest.trim();
+                if tag.is_empty() { None } else { Some(tag.to_string()) }
+            };
+
+            let mut code_lines = Vec::new();
+            for code_line in lines.by_ref() {
+                if code_line.trim_start().starts_with("```") {
+                    break;
+                }
+                code_lines.push(code_line.to_string());
+            }
+
+            let source = code_lines.join("\n");
+            let highlighted = highlight_code(language.as_deref(), &source);
+            blocks.push(Block::CodeBlock { language, lines: highlighted });
+            continue;
+        }
+
+        if trimmed.trim().is_empty() {
+            flush_paragraph(&mut paragraph_buf, &mut blocks);
+            continue;
+        }
+
+        let stripped = trimmed.trim_start();
+
+        if let Some(heading) = stripped.strip_prefix("### ").map(|t| (3, t))
+            .or_else(|| stripped.strip_prefix("## ").map(|t| (2, t)))
+            .or_else(|| stripped.strip_prefix("# ").map(|t| (1, t)))
+        {
+            flush_paragraph(&mut paragraph_buf, &mut blocks);
+            blocks.push(Block::Heading(heading.0, parse_inline(heading.1)));
+            continue;
+        }
+
+        if let Some(quote) = stripped.strip_prefix("> ") {
+            flush_paragraph(&mut paragraph_buf, &mut blocks);
+            blocks.push(Block::Blockquote(parse_inline(quote)));
+            continue;
+        }
+
+        if let Some(item) = stripped.strip_prefix("- ").or_else(|| stripped.strip_prefix("* ")) {
+            flush_paragraph(&mut paragraph_buf, &mut blocks);
+            let spans = parse_inline(item);
+            match blocks.last_mut() {
+                Some(Block::BulletList(items)) => items.push(spans),
+                _ => blocks.push(Block::BulletList(vec![spans])),
+            }
+            continue;
+        }
+
+        if let Some(rest) = numbered_list_item(stripped) {
+            flush_paragraph(&mut paragraph_buf, &mut blocks);
+            let spans = parse_inline(rest);
+            match blocks.last_mut() {
+                Some(Block::NumberedList(items)) => items.push(spans),
+                _ => blocks.push(Block::NumberedList(vec![spans])),
+            }
+            continue;
+        }
+
+        paragraph_buf.push(trimmed);
+    }
+
+    flush_paragraph(&mut paragraph_buf, &mut blocks);
+    blocks
+}
+
+/// `"1. foo"` -> `Some("foo")`; anything not `<digits>. ` returns `None`.
+fn numbered_list_item(line: &str) -> Option<&str> {
+    let dot = line.find(". ")?;
+    if line[..dot].chars().all(|c| c.is_ascii_digit()) && !line[..dot].is_empty() {
+        Some(&line[dot + 2..])
+    } else {
+        None
+    }
+}
+
+/// Splits a line of text into runs, recognizing `**bold**`, `*italic*`,
+/// `` `code` ``, and `[text](url)` hyperlinks.
+fn parse_inline(text: &str) -> Vec<InlineSpan> {
+    let mut spans = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    let mut plain = String::new();
+
+    fn flush_plain(plain: &mut String, spans: &mut Vec<InlineSpan>) {
+        if !plain.is_empty() {
+            spans.push(InlineSpan { text: std::mem::take(plain), bold: false, italic: false, code: false, link: None });
+        }
+    }
+
+    while i < chars.len() {
+        if chars[i] == '`' {
+            if let Some(end) = find_closing(&chars, i + 1, '`', 1) {
+                flush_plain(&mut plain, &mut spans);
+                let inner: String = chars[i + 1..end].iter().collect();
+                spans.push(InlineSpan { text: inner, bold: false, italic: false, code: true, link: None });
+                i = end + 1;
+                continue;
+            }
+        } else if i + 1 < chars.len() && chars[i] == '*' && chars[i + 1] == '*' {
+            if let Some(end) = find_closing(&chars, i + 2, '*', 2) {
+                flush_plain(&mut plain, &mut spans);
+                let inner: String = chars[i + 2..end].iter().collect();
+                spans.push(InlineSpan { text: inner, bold: true, italic: false, code: false, link: None });
+                i = end + 2;
+                continue;
+            }
+        } else if chars[i] == '*' {
+            if let Some(end) = find_closing(&chars, i + 1, '*', 1) {
+                flush_plain(&mut plain, &mut spans);
+                let inner: String = chars[i + 1..end].iter().collect();
+                spans.push(InlineSpan { text: inner, bold: false, italic: true, code: false, link: None });
+                i = end + 1;
+                continue;
+            }
+        } else if chars[i] == '[' {
+            if let Some(label_end) = find_closing(&chars, i + 1, ']', 1) {
+                if chars.get(label_end + 1) == Some(&'(') {
+                    if let Some(url_end) = find_closing(&chars, label_end + 2, ')', 1) {
+                        flush_plain(&mut plain, &mut spans);
+                        let label: String = chars[i + 1..label_end].iter().collect();
+                        let url: String = chars[label_end + 2..url_end].iter().collect();
+                        spans.push(InlineSpan { text: label, bold: false, italic: false, code: false, link: Some(url) });
+                        i = url_end + 1;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        plain.push(chars[i]);
+        i += 1;
+    }
+
+    flush_plain(&mut plain, &mut spans);
+    spans
+}
+
+/// Finds the index of the closing delimiter run (`marker` repeated `width` times)
+/// starting the search at `from`. Returns the index of the first marker char.
+fn find_closing(chars: &[char], from: usize, marker: char, width: usize) -> Option<usize> {
+    let mut j = from;
+    while j + width <= chars.len() {
+        if chars[j..j + width].iter().all(|&c| c == marker) {
+            return Some(j);
+        }
+        j += 1;
+    }
+    None
+}
+
+// ============================================================================
+// Syntax highlighting
+// ============================================================================
+
+/// Runs the matching tree-sitter grammar over a fenced code block's source and maps
+/// each `(byte-range, capture-name)` highlight event into a flat per-line run list.
+/// Unknown/blank language tags, or languages we don't carry a grammar for, fall back
+/// to a single unstyled (plain monospace) run per line.
+fn highlight_code(language: Option<&str>, source: &str) -> Vec<Vec<HighlightedRun>> {
+    let plain = || -> Vec<Vec<HighlightedRun>> {
+        source
+            .lines()
+            .map(|line| vec![HighlightedRun { text: line.to_string(), color: rgb(0x424242) }])
+            .collect()
+    };
+
+    let Some(language) = language else { return plain() };
+    let Some(config) = highlight_config_for(language) else { return plain() };
+
+    let mut highlighter = Highlighter::new();
+    let events = match highlighter.highlight(&config, source.as_bytes(), None, |_| None) {
+        Ok(events) => events,
+        Err(_) => return plain(),
+    };
+
+    let mut runs: Vec<(usize, usize, Option<&'static str>)> = Vec::new();
+    let mut capture_stack: Vec<&'static str> = Vec::new();
+
+    for event in events {
+        match event {
+            Ok(HighlightEvent::HighlightStart(highlight)) => {
+                capture_stack.push(CAPTURE_NAMES[highlight.0]);
+            }
+            Ok(HighlightEvent::HighlightEnd) => {
+                capture_stack.pop();
+            }
+            Ok(HighlightEvent::Source { start, end }) => {
+                runs.push((start, end, capture_stack.last().copied()));
+            }
+            Err(_) => return plain(),
+        }
+    }
+
+    // Stitch byte-range runs back into lines, splitting any run that crosses a '\n'.
+    let mut lines: Vec<Vec<HighlightedRun>> = vec![Vec::new()];
+    for (start, end, capture) in runs {
+        let color = capture.map(capture_color).unwrap_or(rgb(0x424242));
+        let text = &source[start..end];
+        for (idx, segment) in text.split('\n').enumerate() {
+            if idx > 0 {
+                lines.push(Vec::new());
+            }
+            if !segment.is_empty() {
+                lines.last_mut().unwrap().push(HighlightedRun { text: segment.to_string(), color });
+            }
+        }
+    }
+
+    lines
+}
+
+const CAPTURE_NAMES: &[&str] = &[
+    "keyword", "string", "comment", "type", "function", "number", "constant",
+    "variable", "property", "operator", "punctuation",
+];
+
+fn capture_color(name: &str) -> Rgba {
+    match name {
+        "keyword" => rgb(0x9c27b0),
+        "string" => rgb(0x2e7d32),
+        "comment" => rgb(0x9e9e9e),
+        "type" => rgb(0x0277bd),
+        "function" => rgb(0x6a1b9a),
+        "number" | "constant" => rgb(0xef6c00),
+        "variable" | "property" => rgb(0x37474f),
+        "operator" | "punctuation" => rgb(0x616161),
+        _ => rgb(0x424242),
+    }
+}
+
+/// Builds the tree-sitter highlight configuration for a fenced code block's language
+/// tag, aliasing common shorthands (`rs`, `py`, `js`, `ts`, `sh`) to their canonical
+/// grammar. Returns `None` for anything we don't carry a grammar for.
+fn highlight_config_for(language: &str) -> Option<HighlightConfiguration> {
+    let canonical = match language.to_lowercase().as_str() {
+        "rust" | "rs" => "rust",
+        "python" | "py" => "python",
+        "javascript" | "js" | "jsx" => "javascript",
+        "typescript" | "ts" | "tsx" => "typescript",
+        "json" => "json",
+        "bash" | "sh" | "shell" => "bash",
+        _ => return None,
+    };
+
+    let mut config = match canonical {
+        "rust" => HighlightConfiguration::new(
+            tree_sitter_rust::LANGUAGE.into(),
+            "rust",
+            tree_sitter_rust::HIGHLIGHTS_QUERY,
+            "",
+            "",
+        ),
+        "python" => HighlightConfiguration::new(
+            tree_sitter_python::LANGUAGE.into(),
+            "python",
+            tree_sitter_python::HIGHLIGHTS_QUERY,
+            "",
+            "",
+        ),
+        "javascript" => HighlightConfiguration::new(
+            tree_sitter_javascript::LANGUAGE.into(),
+            "javascript",
+            tree_sitter_javascript::HIGHLIGHT_QUERY,
+            "",
+            tree_sitter_javascript::LOCALS_QUERY,
+        ),
+        "typescript" => HighlightConfiguration::new(
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            "typescript",
+            tree_sitter_typescript::HIGHLIGHTS_QUERY,
+            "",
+            "",
+        ),
+        "json" => HighlightConfiguration::new(
+            tree_sitter_json::LANGUAGE.into(),
+            "json",
+            tree_sitter_json::HIGHLIGHTS_QUERY,
+            "",
+            "",
+        ),
+        "bash" => HighlightConfiguration::new(
+            tree_sitter_bash::LANGUAGE.into(),
+            "bash",
+            tree_sitter_bash::HIGHLIGHTS_QUERY,
+            "",
+            "",
+        ),
+        _ => unreachable!(),
+    }
+    .ok()?;
+
+    config.configure(CAPTURE_NAMES);
+    Some(config)
+}
+
+// ============================================================================
+// gpui rendering
+// ============================================================================
+
+/// Renders markdown content as a column of styled blocks.
+pub fn render_markdown(content: &str) -> Div {
+    div()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .children(parse_blocks(content).into_iter().map(render_block))
+}
+
+/// Same parser/renderer as `render_markdown`, but appends the streaming cursor glyph
+/// so content being received still shows the existing blinking-cursor affordance. An
+/// unterminated trailing fence is handled by the parser itself (rest-of-stream is
+/// code), so the highlight never flickers as more fence-delimited tokens arrive.
+pub fn render_markdown_streaming(content: &str) -> Div {
+    render_markdown(&format!("{}▊", content))
+}
+
+fn render_block(block: Block) -> Div {
+    match block {
+        Block::Heading(level, spans) => {
+            let size = match level {
+                1 => 1.25,
+                2 => 1.1,
+                _ => 1.0,
+            };
+            div()
+                .flex()
+                .flex_wrap()
+                .font_weight(FontWeight::BOLD)
+                .text_size(rems(size))
+                .text_color(rgb(0x212121))
+                .children(spans.into_iter().map(render_inline))
+        }
+        Block::Paragraph(spans) => div()
+            .flex()
+            .flex_wrap()
+            .text_sm()
+            .text_color(rgb(0x212121))
+            .line_height(relative(1.5))
+            .children(spans.into_iter().map(render_inline)),
+        Block::BulletList(items) => div()
+            .flex()
+            .flex_col()
+            .gap_0p5()
+            .pl_2()
+            .children(items.into_iter().map(|spans| {
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .text_sm()
+                    .text_color(rgb(0x212121))
+                    .line_height(relative(1.5))
+                    .child("• ")
+                    .children(spans.into_iter().map(render_inline))
+            })),
+        Block::NumberedList(items) => div()
+            .flex()
+            .flex_col()
+            .gap_0p5()
+            .pl_2()
+            .children(items.into_iter().enumerate().map(|(i, spans)| {
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .text_sm()
+                    .text_color(rgb(0x212121))
+                    .line_height(relative(1.5))
+                    .child(format!("{}. ", i + 1))
+                    .children(spans.into_iter().map(render_inline))
+            })),
+        Block::Blockquote(spans) => div()
+            .flex()
+            .flex_wrap()
+            .pl_2()
+            .border_l_2()
+            .border_color(rgb(0xbdbdbd))
+            .text_sm()
+            .text_color(rgb(0x616161))
+            .line_height(relative(1.5))
+            .children(spans.into_iter().map(render_inline)),
+        Block::CodeBlock { lines, .. } => div()
+            .flex()
+            .flex_col()
+            .px_3()
+            .py_2()
+            .bg(rgb(0xfafafa))
+            .border_1()
+            .border_color(rgb(0xe0e0e0))
+            .rounded(px(4.0))
+            .text_xs()
+            .font_family("monospace")
+            .line_height(relative(1.5))
+            .overflow_x_hidden()
+            .children(lines.into_iter().map(render_code_line)),
+    }
+}
+
+fn render_inline(span: InlineSpan) -> Div {
+    if let Some(url) = span.link {
+        return div()
+            .id(SharedString::from(format!("md-link-{}", url)))
+            .text_color(rgb(0x1976d2))
+            .cursor_pointer()
+            .hover(|style| style.text_color(rgb(0x0d47a1)))
+            .on_click(move |_event, _window, cx| cx.open_url(&url))
+            .child(span.text);
+    }
+
+    let mut el = div();
+    if span.code {
+        el = el
+            .px_1()
+            .bg(rgb(0xeeeeee))
+            .rounded(px(3.0))
+            .font_family("monospace")
+            .text_color(rgb(0xc62828));
+    }
+    if span.bold {
+        el = el.font_weight(FontWeight::BOLD);
+    }
+    if span.italic {
+        el = el.italic();
+    }
+    el.child(span.text)
+}
+
+fn render_code_line(runs: Vec<HighlightedRun>) -> Div {
+    div()
+        .flex()
+        .flex_wrap()
+        .children(runs.into_iter().map(|run| div().text_color(run.color).child(run.text)))
+}
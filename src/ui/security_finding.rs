@@ -0,0 +1,547 @@
+// A single security finding rendered as a collapsible card: the header
+// (icon/severity/title) and file location are always visible, while the
+// description and recommendation fold away behind a "Show details" toggle
+// so a scan with dozens of findings doesn't force the user to scroll past
+// all of them at once. See `security_panel` for the grouped/filterable view
+// that lays several of these out together.
+use gpui::*;
+use gpui::prelude::*;
+
+use super::markdown::render_markdown;
+use super::theme::active_theme;
+
+/// A `file_path:line_number` pair, carried by [`FindingLocationClicked`] so
+/// the host app can jump to the spot in the editor.
+#[derive(Debug, Clone)]
+pub struct FileLocation {
+    pub file_path: String,
+    pub line_number: Option<u32>,
+}
+
+/// Emitted when the user clicks a finding's location chip. The host app
+/// subscribes to a [`SecurityFindingCard`] (or the panel that owns it) via
+/// `cx.subscribe` to react to this.
+#[derive(Debug, Clone)]
+pub struct FindingLocationClicked(pub FileLocation);
+
+/// How the user chose to suppress a finding via the card's action row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DismissalReason {
+    /// The user acknowledged the finding and wants it out of the way.
+    Dismissed,
+    /// The user judged the finding a false positive, not just noise to hide.
+    FalsePositive,
+}
+
+/// Emitted when the user dismisses or marks a finding as a false positive.
+/// The card fades itself out locally; the host app subscribes via
+/// `cx.subscribe` to persist the decision (e.g. so it stays suppressed
+/// across sessions).
+#[derive(Debug, Clone)]
+pub struct FindingDismissed(pub DismissalReason);
+
+/// A vulnerability's risk level, in triage order. Scanners report this as a
+/// free-form string (see `TimelineEvent::SecurityFinding::severity`); parse
+/// it with [`Severity::parse`] at the point where that string enters the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+    Info,
+}
+
+impl Severity {
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "critical" => Severity::Critical,
+            "high" => Severity::High,
+            "medium" => Severity::Medium,
+            "low" => Severity::Low,
+            _ => Severity::Info,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Critical => "Critical",
+            Severity::High => "High",
+            Severity::Medium => "Medium",
+            Severity::Low => "Low",
+            Severity::Info => "Info",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Critical => "🚨",
+            Severity::High => "⚠️",
+            Severity::Medium => "⚡",
+            Severity::Low => "ℹ️",
+            Severity::Info => "📋",
+        }
+    }
+
+    /// All variants in triage order, most urgent first.
+    pub const ALL: [Severity; 5] = [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+        Severity::Info,
+    ];
+
+    /// The key `Theme::severity_colors` expects.
+    pub(crate) fn theme_key(&self) -> &'static str {
+        match self {
+            Severity::Critical => "critical",
+            Severity::High => "high",
+            Severity::Medium => "medium",
+            Severity::Low => "low",
+            Severity::Info => "info",
+        }
+    }
+}
+
+/// How sure the scanner is that a finding is a true positive. Also reported
+/// as a free-form string; parse with [`Confidence::parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Confidence {
+    Confirmed,
+    High,
+    Medium,
+    Low,
+    Unknown,
+}
+
+impl Confidence {
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "confirmed" => Confidence::Confirmed,
+            "high" => Confidence::High,
+            "medium" => Confidence::Medium,
+            "low" => Confidence::Low,
+            _ => Confidence::Unknown,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Confidence::Confirmed => "Confirmed",
+            Confidence::High => "High",
+            Confidence::Medium => "Medium",
+            Confidence::Low => "Low",
+            Confidence::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Which external database an [`Identifier`] chip points at, so we know how
+/// to derive a lookup URL when the scanner didn't supply one directly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentifierKind {
+    Cwe,
+    Cve,
+    RustSec,
+    Ghsa,
+    /// Anything else (an internal scanner ID, an advisory we don't special-case, ...).
+    /// Carries the label to show instead of a fixed one.
+    Other(String),
+}
+
+impl IdentifierKind {
+    /// Parses a scanner-reported `kind` string (case-insensitive).
+    pub fn parse(kind: &str) -> Self {
+        match kind.to_lowercase().as_str() {
+            "cwe" => IdentifierKind::Cwe,
+            "cve" => IdentifierKind::Cve,
+            "rustsec" => IdentifierKind::RustSec,
+            "ghsa" => IdentifierKind::Ghsa,
+            other => IdentifierKind::Other(other.to_string()),
+        }
+    }
+
+    /// Infers a kind from the shape of a bare identifier value, for IDs that
+    /// arrive without an explicit `kind` (e.g. `vulnerability_id`).
+    pub fn infer(value: &str) -> Self {
+        let upper = value.to_uppercase();
+        if upper.starts_with("CVE-") {
+            IdentifierKind::Cve
+        } else if upper.starts_with("RUSTSEC-") {
+            IdentifierKind::RustSec
+        } else if upper.starts_with("CWE-") {
+            IdentifierKind::Cwe
+        } else if upper.starts_with("GHSA-") {
+            IdentifierKind::Ghsa
+        } else {
+            IdentifierKind::Other("ID".to_string())
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            IdentifierKind::Cwe => "CWE",
+            IdentifierKind::Cve => "CVE",
+            IdentifierKind::RustSec => "RUSTSEC",
+            IdentifierKind::Ghsa => "GHSA",
+            IdentifierKind::Other(label) => label,
+        }
+    }
+
+    /// A canonical lookup URL for `value`, used when the scanner didn't
+    /// supply an explicit one. Digits-only CWE values (`"79"`) and
+    /// `CWE-`-prefixed ones both resolve to the same MITRE page.
+    pub(crate) fn canonical_url(&self, value: &str) -> Option<String> {
+        match self {
+            IdentifierKind::Cwe => {
+                let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+                (!digits.is_empty()).then(|| format!("https://cwe.mitre.org/data/definitions/{}.html", digits))
+            }
+            IdentifierKind::Cve => Some(format!("https://vulnerability.circl.lu/vuln/{}", value)),
+            IdentifierKind::RustSec => Some(format!("https://rustsec.org/advisories/{}.html", value)),
+            IdentifierKind::Ghsa => Some(format!("https://github.com/advisories/{}", value)),
+            IdentifierKind::Other(_) => None,
+        }
+    }
+}
+
+/// A single external reference shown as a clickable chip (CWE/CVE/RUSTSEC/...).
+#[derive(Debug, Clone)]
+pub struct Identifier {
+    pub kind: IdentifierKind,
+    pub value: String,
+    /// Overrides the kind's derived canonical URL when the scanner already
+    /// supplied one.
+    pub url: Option<String>,
+}
+
+impl Identifier {
+    pub fn new(kind: IdentifierKind, value: impl Into<String>, url: Option<String>) -> Self {
+        Self { kind, value: value.into(), url }
+    }
+
+    fn resolved_url(&self) -> Option<String> {
+        self.url.clone().or_else(|| self.kind.canonical_url(&self.value))
+    }
+}
+
+/// A titled external link, shown in the card's "Links / References" section.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub title: String,
+    pub url: String,
+}
+
+/// Plain data for a single finding, mirroring `TimelineEvent::SecurityFinding`'s
+/// fields without the timestamp - the shape [`SecurityFindingCard`] and
+/// `security_panel::SecurityFindingsPanel` are built from.
+#[derive(Debug, Clone)]
+pub struct SecurityFindingData {
+    pub vulnerability_id: String,
+    pub severity: Severity,
+    pub confidence: Option<Confidence>,
+    pub title: String,
+    pub description: String,
+    pub file_path: String,
+    pub line_number: Option<u32>,
+    pub identifiers: Vec<Identifier>,
+    pub recommendation: String,
+    pub affected_instances: Vec<FileLocation>,
+    pub references: Vec<Reference>,
+}
+
+pub struct SecurityFindingCard {
+    data: SecurityFindingData,
+    expanded: bool,
+    instances_expanded: bool,
+    references_expanded: bool,
+    dismissal: Option<DismissalReason>,
+}
+
+impl SecurityFindingCard {
+    pub fn new(data: SecurityFindingData, expanded: bool) -> Self {
+        Self { data, expanded, instances_expanded: false, references_expanded: false, dismissal: None }
+    }
+}
+
+impl EventEmitter<FindingLocationClicked> for SecurityFindingCard {}
+impl EventEmitter<FindingDismissed> for SecurityFindingCard {}
+
+impl Render for SecurityFindingCard {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = active_theme(cx);
+        let (bg_color, border_color) = theme.severity_colors(self.data.severity.theme_key());
+        let icon = self.data.severity.icon();
+        let badge_label = match &self.data.confidence {
+            Some(confidence) => format!("{} ({} confidence)", self.data.severity.label(), confidence.label()),
+            None => self.data.severity.label().to_string(),
+        };
+        let location = FileLocation {
+            file_path: self.data.file_path.clone(),
+            line_number: self.data.line_number,
+        };
+        // The scanner's own `vulnerability_id` often duplicates one of the
+        // structured `identifiers` (e.g. both carry the same CVE). Only
+        // add it as its own chip when it isn't already covered.
+        let mut chip_identifiers = self.data.identifiers.clone();
+        if !self.data.vulnerability_id.is_empty()
+            && !chip_identifiers.iter().any(|identifier| identifier.value == self.data.vulnerability_id)
+        {
+            chip_identifiers.push(Identifier::new(
+                IdentifierKind::infer(&self.data.vulnerability_id),
+                self.data.vulnerability_id.clone(),
+                None,
+            ));
+        }
+
+        div()
+            .max_w(rems(40.0))
+            .px_3()
+            .py_2()
+            .bg(bg_color)
+            .border_1()
+            .border_color(border_color)
+            .rounded_md()
+            .opacity(if self.dismissal.is_some() { 0.4 } else { 1.0 })
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                // Header: Icon + Severity + Title
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_lg().child(icon))
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_0p5()
+                            .child(
+                                div()
+                                    .px_1p5()
+                                    .py_0p5()
+                                    .rounded_full()
+                                    .bg(border_color)
+                                    .text_xs()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(rgb(0xffffff))
+                                    .child(badge_label),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(theme.primary_text)
+                                    .child(self.data.title.clone()),
+                            ),
+                    ),
+            )
+            .child(
+                // File location, clickable so the host app can open it.
+                div()
+                    .id("finding-location")
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .cursor_pointer()
+                    .text_color(theme.mono_text)
+                    .hover(|style| style.text_color(theme.link_accent))
+                    .child(div().text_xs().child("📄"))
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_family("monospace")
+                            .child(if let Some(line) = self.data.line_number {
+                                format!("{}:{}", self.data.file_path, line)
+                            } else {
+                                self.data.file_path.clone()
+                            }),
+                    )
+                    .on_click(cx.listener(move |_this, _event, _window, cx| {
+                        cx.emit(FindingLocationClicked(location.clone()));
+                    })),
+            )
+            .when(!chip_identifiers.is_empty(), |container| {
+                container.child(
+                    div().flex().flex_wrap().gap_1().children(chip_identifiers.iter().map(|identifier| {
+                        let label = format!("{}: {}", identifier.kind.label(), identifier.value);
+                        let url = identifier.resolved_url();
+                        let chip = div()
+                            .px_1p5()
+                            .py_0p5()
+                            .rounded_sm()
+                            .bg(theme.severity_unknown_bg)
+                            .border_1()
+                            .border_color(theme.severity_unknown_border)
+                            .text_xs()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(theme.muted_text)
+                            .child(label);
+
+                        match url {
+                            Some(url) => chip
+                                .id(SharedString::from(format!("finding-identifier-{}-{}", identifier.kind.label(), identifier.value)))
+                                .cursor_pointer()
+                                .hover(|style| style.text_color(theme.link_accent))
+                                .on_click(move |_event, _window, cx| cx.open_url(&url)),
+                            None => chip,
+                        }
+                    })),
+                )
+            })
+            .child(
+                div()
+                    .id("finding-toggle")
+                    .text_xs()
+                    .text_color(theme.link_accent)
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(theme.link_accent_hover))
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.expanded = !this.expanded;
+                        cx.notify();
+                    }))
+                    .child(if self.expanded { "Hide details" } else { "Show details" }),
+            )
+            .when(self.expanded, |container| {
+                container
+                    .child(render_markdown(&self.data.description))
+                    .child(
+                        div()
+                            .mt_2()
+                            .pt_2()
+                            .border_t_1()
+                            .border_color(border_color)
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.mono_text)
+                                    .child("💡 Recommendation"),
+                            )
+                            .child(render_markdown(&self.data.recommendation)),
+                    )
+                    .when(!self.data.affected_instances.is_empty(), |container| {
+                        container.child(
+                            div()
+                                .id("finding-instances-toggle")
+                                .text_xs()
+                                .text_color(theme.link_accent)
+                                .cursor_pointer()
+                                .hover(|style| style.text_color(theme.link_accent_hover))
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.instances_expanded = !this.instances_expanded;
+                                    cx.notify();
+                                }))
+                                .child(format!(
+                                    "{} Affected instances ({})",
+                                    if self.instances_expanded { "▼" } else { "▶" },
+                                    self.data.affected_instances.len()
+                                )),
+                        )
+                    })
+                    .when(self.instances_expanded, |container| {
+                        container.child(div().flex().flex_col().gap_0p5().pl_2().children(
+                            self.data.affected_instances.iter().map(|instance| {
+                                div()
+                                    .text_xs()
+                                    .font_family("monospace")
+                                    .text_color(theme.mono_text)
+                                    .child(if let Some(line) = instance.line_number {
+                                        format!("{}:{}", instance.file_path, line)
+                                    } else {
+                                        instance.file_path.clone()
+                                    })
+                            }),
+                        ))
+                    })
+                    .when(!self.data.references.is_empty(), |container| {
+                        container.child(
+                            div()
+                                .id("finding-references-toggle")
+                                .text_xs()
+                                .text_color(theme.link_accent)
+                                .cursor_pointer()
+                                .hover(|style| style.text_color(theme.link_accent_hover))
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.references_expanded = !this.references_expanded;
+                                    cx.notify();
+                                }))
+                                .child(format!(
+                                    "{} Links / References ({})",
+                                    if self.references_expanded { "▼" } else { "▶" },
+                                    self.data.references.len()
+                                )),
+                        )
+                    })
+                    .when(self.references_expanded, |container| {
+                        container.child(div().flex().flex_col().gap_0p5().pl_2().children(
+                            self.data.references.iter().map(|reference| {
+                                let url = reference.url.clone();
+                                div()
+                                    .id(SharedString::from(format!("finding-reference-{}", reference.url)))
+                                    .text_xs()
+                                    .text_color(theme.link_accent)
+                                    .cursor_pointer()
+                                    .hover(|style| style.text_color(theme.link_accent_hover))
+                                    .on_click(move |_event, _window, cx| cx.open_url(&url))
+                                    .child(reference.title.clone())
+                            }),
+                        ))
+                    })
+            })
+            .when(self.dismissal.is_none(), |container| {
+                container.child(
+                    div()
+                        .mt_1()
+                        .flex()
+                        .gap_2()
+                        .child(
+                            div()
+                                .id("finding-dismiss")
+                                .text_xs()
+                                .text_color(theme.muted_text)
+                                .cursor_pointer()
+                                .hover(|style| style.text_color(theme.primary_text))
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.dismissal = Some(DismissalReason::Dismissed);
+                                    cx.emit(FindingDismissed(DismissalReason::Dismissed));
+                                    cx.notify();
+                                }))
+                                .child("Dismiss"),
+                        )
+                        .child(
+                            div()
+                                .id("finding-mark-false-positive")
+                                .text_xs()
+                                .text_color(theme.muted_text)
+                                .cursor_pointer()
+                                .hover(|style| style.text_color(theme.primary_text))
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.dismissal = Some(DismissalReason::FalsePositive);
+                                    cx.emit(FindingDismissed(DismissalReason::FalsePositive));
+                                    cx.notify();
+                                }))
+                                .child("Mark false positive"),
+                        ),
+                )
+            })
+    }
+}
+
+/// Spawns a fresh [`SecurityFindingCard`] entity for `data`. `expanded`
+/// controls whether the description/recommendation start visible - callers
+/// rendering a single finding inline (e.g. a live timeline event) typically
+/// pass `true`, while a panel triaging dozens at once starts them collapsed.
+pub fn security_finding_card(
+    cx: &mut App,
+    data: SecurityFindingData,
+    expanded: bool,
+) -> Entity<SecurityFindingCard> {
+    cx.new(|_cx| SecurityFindingCard::new(data, expanded))
+}
@@ -0,0 +1,167 @@
+// Groups a batch of security findings into a triage view: a header strip of
+// per-severity counts that double as toggle filters, and a collapsible
+// section per severity so the Low/Info noise can be folded away. Each
+// finding inside is rendered by `security_finding::SecurityFindingCard`,
+// which owns its own expand/collapse state; this panel only tracks which
+// severities are shown and which groups are folded.
+use std::collections::HashSet;
+
+use gpui::*;
+use gpui::prelude::*;
+
+use super::security_finding::{
+    security_finding_card, FindingDismissed, FindingLocationClicked, SecurityFindingCard, SecurityFindingData,
+    Severity,
+};
+use super::theme::active_theme;
+
+struct FindingGroup {
+    severity: Severity,
+    cards: Vec<Entity<SecurityFindingCard>>,
+}
+
+pub struct SecurityFindingsPanel {
+    groups: Vec<FindingGroup>,
+    active_severities: HashSet<Severity>,
+    collapsed_severities: HashSet<Severity>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl SecurityFindingsPanel {
+    pub fn new(findings: Vec<SecurityFindingData>, cx: &mut Context<Self>) -> Self {
+        let mut groups: Vec<FindingGroup> = Severity::ALL
+            .iter()
+            .map(|&severity| FindingGroup { severity, cards: Vec::new() })
+            .collect();
+        let mut subscriptions = Vec::new();
+
+        for data in findings {
+            let severity = data.severity;
+            let card = security_finding_card(cx, data, false);
+            subscriptions.push(cx.subscribe(&card, |_this, _card, event: &FindingLocationClicked, cx| {
+                cx.emit(event.clone());
+            }));
+            subscriptions.push(cx.subscribe(&card, |_this, _card, event: &FindingDismissed, cx| {
+                cx.emit(event.clone());
+            }));
+            groups.iter_mut().find(|g| g.severity == severity).unwrap().cards.push(card);
+        }
+
+        // Severities with no findings stay in `groups` (so the header strip
+        // can still show a zeroed-out chip) but start collapsed since
+        // there's nothing to show.
+        let collapsed_severities = groups
+            .iter()
+            .filter(|g| g.cards.is_empty())
+            .map(|g| g.severity)
+            .collect();
+
+        Self {
+            groups,
+            active_severities: Severity::ALL.iter().copied().collect(),
+            collapsed_severities,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn toggle_severity_filter(&mut self, severity: Severity) {
+        if !self.active_severities.remove(&severity) {
+            self.active_severities.insert(severity);
+        }
+    }
+
+    fn toggle_group_collapsed(&mut self, severity: Severity) {
+        if !self.collapsed_severities.remove(&severity) {
+            self.collapsed_severities.insert(severity);
+        }
+    }
+}
+
+impl EventEmitter<FindingLocationClicked> for SecurityFindingsPanel {}
+impl EventEmitter<FindingDismissed> for SecurityFindingsPanel {}
+
+impl Render for SecurityFindingsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = active_theme(cx);
+
+        let header = div().flex().gap_2().px_2().py_1().children(self.groups.iter().map(|group| {
+            let severity = group.severity;
+            let count = group.cards.len();
+            let active = self.active_severities.contains(&severity);
+            let (bg, border) = theme.severity_colors(severity.theme_key());
+
+            div()
+                .id(("severity-filter", severity.theme_key()))
+                .px_2()
+                .py_1()
+                .rounded_md()
+                .border_1()
+                .border_color(border)
+                .bg(bg)
+                .opacity(if active { 1.0 } else { 0.4 })
+                .cursor_pointer()
+                .text_xs()
+                .font_weight(FontWeight::BOLD)
+                .text_color(theme.primary_text)
+                .child(format!("{} {}", severity.label(), count))
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    this.toggle_severity_filter(severity);
+                    cx.notify();
+                }))
+        }));
+
+        let groups = div().flex().flex_col().gap_2().children(
+            self.groups
+                .iter()
+                .filter(|group| self.active_severities.contains(&group.severity) && !group.cards.is_empty())
+                .map(|group| {
+                    let severity = group.severity;
+                    let collapsed = self.collapsed_severities.contains(&severity);
+                    let (_, border) = theme.severity_colors(severity.theme_key());
+
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .child(
+                            div()
+                                .id(("severity-group-toggle", severity.theme_key()))
+                                .flex()
+                                .items_center()
+                                .gap_1()
+                                .px_2()
+                                .cursor_pointer()
+                                .text_xs()
+                                .font_weight(FontWeight::BOLD)
+                                .text_color(border)
+                                .child(if collapsed { "▶" } else { "▼" })
+                                .child(format!("{} ({})", severity.label(), group.cards.len()))
+                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                    this.toggle_group_collapsed(severity);
+                                    cx.notify();
+                                })),
+                        )
+                        .when(!collapsed, |container| {
+                            container.child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .gap_2()
+                                    .pl_4()
+                                    .children(group.cards.iter().cloned()),
+                            )
+                        })
+                }),
+        );
+
+        div().flex().flex_col().gap_2().child(header).child(groups)
+    }
+}
+
+/// Spawns a fresh [`SecurityFindingsPanel`] grouping `findings` by severity.
+pub fn security_findings_panel(
+    cx: &mut App,
+    findings: Vec<SecurityFindingData>,
+) -> Entity<SecurityFindingsPanel> {
+    cx.new(|cx| SecurityFindingsPanel::new(findings, cx))
+}
@@ -0,0 +1,218 @@
+// Centralized semantic theme for the timeline renderers in `components.rs`.
+// Replaces the hardcoded `rgb(0x...)` literals that used to live in every
+// render function with named roles, stored as a GPUI global so a single
+// `cx.set_global(Theme::dark())` swap re-skins the whole timeline.
+use gpui::{rgb, Global, Rgba};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub primary_text: Rgba,
+    pub body_text: Rgba,
+    pub mono_text: Rgba,
+    pub muted_text: Rgba,
+    pub faint_text: Rgba,
+
+    pub user_bubble_bg: Rgba,
+    pub user_bubble_border: Rgba,
+    pub user_accent: Rgba,
+
+    pub assistant_bubble_bg: Rgba,
+    pub assistant_bubble_border: Rgba,
+    pub assistant_accent: Rgba,
+
+    pub thought_bubble_bg: Rgba,
+    pub thought_bubble_border: Rgba,
+    pub thought_accent: Rgba,
+
+    pub tool_bg: Rgba,
+    pub tool_border: Rgba,
+    pub tool_accent: Rgba,
+    pub status_running: Rgba,
+    pub status_completed: Rgba,
+    pub status_failed: Rgba,
+
+    pub mcp_connected_bg: Rgba,
+    pub mcp_connected_border: Rgba,
+    pub mcp_disconnected_bg: Rgba,
+    pub mcp_disconnected_border: Rgba,
+    pub mcp_disconnected_accent: Rgba,
+
+    pub agent_prompt_bg: Rgba,
+    pub agent_prompt_border: Rgba,
+    pub agent_prompt_accent: Rgba,
+    pub agent_prompt_code_bg: Rgba,
+    pub agent_prompt_code_border: Rgba,
+
+    pub severity_critical_bg: Rgba,
+    pub severity_critical_border: Rgba,
+    pub severity_high_bg: Rgba,
+    pub severity_high_border: Rgba,
+    pub severity_medium_bg: Rgba,
+    pub severity_medium_border: Rgba,
+    pub severity_low_bg: Rgba,
+    pub severity_low_border: Rgba,
+    pub severity_unknown_bg: Rgba,
+    pub severity_unknown_border: Rgba,
+
+    pub crash_bg: Rgba,
+    pub crash_border: Rgba,
+    pub crash_accent: Rgba,
+    pub crash_code_bg: Rgba,
+
+    pub link_accent: Rgba,
+    pub link_accent_hover: Rgba,
+    pub copy_success_accent: Rgba,
+}
+
+impl Theme {
+    /// The original hardcoded palette, kept as the default light theme.
+    pub fn light() -> Self {
+        Self {
+            primary_text: rgb(0x212121),
+            body_text: rgb(0x424242),
+            mono_text: rgb(0x616161),
+            muted_text: rgb(0x757575),
+            faint_text: rgb(0x9e9e9e),
+
+            user_bubble_bg: rgb(0xe8f2ff),
+            user_bubble_border: rgb(0x90caf9),
+            user_accent: rgb(0x1976d2),
+
+            assistant_bubble_bg: rgb(0xf0f4f8),
+            assistant_bubble_border: rgb(0xcfd8dc),
+            assistant_accent: rgb(0x546e7a),
+
+            thought_bubble_bg: rgb(0xfff8e1),
+            thought_bubble_border: rgb(0xffd54f),
+            thought_accent: rgb(0xf57c00),
+
+            tool_bg: rgb(0xe8f5e9),
+            tool_border: rgb(0x81c784),
+            tool_accent: rgb(0x2e7d32),
+            status_running: rgb(0x1976d2),
+            status_completed: rgb(0x388e3c),
+            status_failed: rgb(0xd32f2f),
+
+            mcp_connected_bg: rgb(0xe3f2fd),
+            mcp_connected_border: rgb(0x90caf9),
+            mcp_disconnected_bg: rgb(0xfce4ec),
+            mcp_disconnected_border: rgb(0xf48fb1),
+            mcp_disconnected_accent: rgb(0xc2185b),
+
+            agent_prompt_bg: rgb(0xfff9c4),
+            agent_prompt_border: rgb(0xfff176),
+            agent_prompt_accent: rgb(0xf57f17),
+            agent_prompt_code_bg: rgb(0xfffde7),
+            agent_prompt_code_border: rgb(0xfbc02d),
+
+            severity_critical_bg: rgb(0xffebee),
+            severity_critical_border: rgb(0xef5350),
+            severity_high_bg: rgb(0xfff3e0),
+            severity_high_border: rgb(0xfb8c00),
+            severity_medium_bg: rgb(0xfff9c4),
+            severity_medium_border: rgb(0xfdd835),
+            severity_low_bg: rgb(0xe8f5e9),
+            severity_low_border: rgb(0x66bb6a),
+            severity_unknown_bg: rgb(0xf5f5f5),
+            severity_unknown_border: rgb(0x9e9e9e),
+
+            crash_bg: rgb(0xffebee),
+            crash_border: rgb(0xef5350),
+            crash_accent: rgb(0xc62828),
+            crash_code_bg: rgb(0xfff5f5),
+
+            link_accent: rgb(0x1976d2),
+            link_accent_hover: rgb(0x0d47a1),
+            copy_success_accent: rgb(0x2e7d32),
+        }
+    }
+
+    /// Dark variant: desaturated near-black surfaces with the same accent hues,
+    /// lightened just enough to stay legible on a dark background.
+    pub fn dark() -> Self {
+        Self {
+            primary_text: rgb(0xe8e8e8),
+            body_text: rgb(0xc9c9c9),
+            mono_text: rgb(0x9e9e9e),
+            muted_text: rgb(0x8a8a8a),
+            faint_text: rgb(0x707070),
+
+            user_bubble_bg: rgb(0x16324a),
+            user_bubble_border: rgb(0x2d5c85),
+            user_accent: rgb(0x64b5f6),
+
+            assistant_bubble_bg: rgb(0x262b2f),
+            assistant_bubble_border: rgb(0x3c444a),
+            assistant_accent: rgb(0x90a4ae),
+
+            thought_bubble_bg: rgb(0x332c10),
+            thought_bubble_border: rgb(0x6b5a1e),
+            thought_accent: rgb(0xffb74d),
+
+            tool_bg: rgb(0x1b2e1d),
+            tool_border: rgb(0x3f6b42),
+            tool_accent: rgb(0x81c784),
+            status_running: rgb(0x64b5f6),
+            status_completed: rgb(0x81c784),
+            status_failed: rgb(0xef5350),
+
+            mcp_connected_bg: rgb(0x15263a),
+            mcp_connected_border: rgb(0x2d5c85),
+            mcp_disconnected_bg: rgb(0x3a1a26),
+            mcp_disconnected_border: rgb(0x7a3a54),
+            mcp_disconnected_accent: rgb(0xf06292),
+
+            agent_prompt_bg: rgb(0x332f14),
+            agent_prompt_border: rgb(0x6b611f),
+            agent_prompt_accent: rgb(0xffd54f),
+            agent_prompt_code_bg: rgb(0x26230f),
+            agent_prompt_code_border: rgb(0x5c531c),
+
+            severity_critical_bg: rgb(0x3a1515),
+            severity_critical_border: rgb(0xef5350),
+            severity_high_bg: rgb(0x3a2a10),
+            severity_high_border: rgb(0xfb8c00),
+            severity_medium_bg: rgb(0x332f14),
+            severity_medium_border: rgb(0xfdd835),
+            severity_low_bg: rgb(0x1b2e1d),
+            severity_low_border: rgb(0x66bb6a),
+            severity_unknown_bg: rgb(0x2a2a2a),
+            severity_unknown_border: rgb(0x9e9e9e),
+
+            crash_bg: rgb(0x3a1515),
+            crash_border: rgb(0xef5350),
+            crash_accent: rgb(0xef9a9a),
+            crash_code_bg: rgb(0x2a1414),
+
+            link_accent: rgb(0x64b5f6),
+            link_accent_hover: rgb(0x90caf9),
+            copy_success_accent: rgb(0x81c784),
+        }
+    }
+
+    /// Maps a scan-severity label onto this theme's (bg, border) pair, falling
+    /// back to the "unknown" role for anything not recognized.
+    pub fn severity_colors(&self, severity: &str) -> (Rgba, Rgba) {
+        match severity.to_lowercase().as_str() {
+            "critical" => (self.severity_critical_bg, self.severity_critical_border),
+            "high" => (self.severity_high_bg, self.severity_high_border),
+            "medium" => (self.severity_medium_bg, self.severity_medium_border),
+            "low" => (self.severity_low_bg, self.severity_low_border),
+            _ => (self.severity_unknown_bg, self.severity_unknown_border),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl Global for Theme {}
+
+/// Reads the active theme global, defaulting to [`Theme::light`] if none was
+/// installed yet (e.g. in tests that render components without `cx.set_global`).
+pub fn active_theme(cx: &gpui::App) -> Theme {
+    cx.try_global::<Theme>().copied().unwrap_or_default()
+}
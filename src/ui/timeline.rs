@@ -1,12 +1,14 @@
 // Timeline renderer - chronological trajectory display (Perplexity-style)
 use gpui::*;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use std::collections::HashMap;
 
-use crate::types::TimelineEvent;
+use crate::types::{CommitActivity, TimelineEvent};
 use super::components::render_timeline_event;
 
 /// Renders the full chronological timeline of events
 /// Events are sorted by timestamp ascending (oldest first)
-pub fn render_timeline(events: &[TimelineEvent]) -> Stateful<Div> {
+pub fn render_timeline(events: &[TimelineEvent], cx: &mut App) -> Stateful<Div> {
     // Sort events chronologically
     let mut sorted_events = events.to_vec();
     sorted_events.sort_by_key(|e| e.timestamp());
@@ -19,6 +21,185 @@ pub fn render_timeline(events: &[TimelineEvent]) -> Stateful<Div> {
         .children(
             sorted_events
                 .iter()
-                .map(|event| render_timeline_event(event))
+                .map(|event| render_timeline_event(event, cx))
         )
 }
+
+// ============================================================================
+// Contribution Heatmap / Punch Card
+// ============================================================================
+
+const HEATMAP_DAYS: i64 = 365;
+const HEATMAP_BUCKETS: usize = 5;
+
+/// Maps a raw commit count to one of `HEATMAP_BUCKETS` intensity levels by quantile
+/// against the observed max for the window, so a quiet repo and a busy one both
+/// produce a readable gradient instead of everything maxing out (or nothing lighting up).
+fn intensity_bucket(count: usize, max_count: usize) -> usize {
+    if count == 0 || max_count == 0 {
+        return 0;
+    }
+    let ratio = count as f32 / max_count as f32;
+    ((ratio * (HEATMAP_BUCKETS - 1) as f32).ceil() as usize).clamp(1, HEATMAP_BUCKETS - 1)
+}
+
+fn bucket_color(bucket: usize) -> Rgba {
+    match bucket {
+        0 => rgb(0xebedf0),
+        1 => rgb(0x9be9a8),
+        2 => rgb(0x40c463),
+        3 => rgb(0x30a14e),
+        _ => rgb(0x216e39),
+    }
+}
+
+/// Renders a GitHub-style calendar heatmap for the last 365 days, driven by raw
+/// `TimelineEvent` timestamps (messages, tool calls, findings — whatever's in the session).
+pub fn render_heatmap(events: &[TimelineEvent]) -> Div {
+    let timestamps: Vec<i64> = events.iter().map(|e| e.timestamp()).collect();
+    render_calendar_heatmap(&timestamps)
+}
+
+/// Renders the same calendar heatmap plus an hour-vs-weekday punch card, driven by
+/// actual commit activity from `GitAnalysis::commit_activity`.
+pub fn render_commit_heatmap(activity: &[CommitActivity]) -> Div {
+    let timestamps: Vec<i64> = activity.iter().map(|c| c.timestamp).collect();
+
+    div()
+        .flex()
+        .flex_col()
+        .gap_4()
+        .child(render_calendar_heatmap(&timestamps))
+        .child(render_punch_card(&timestamps))
+}
+
+fn render_calendar_heatmap(timestamps: &[i64]) -> Div {
+    let now = timestamps.iter().max().copied()
+        .and_then(|ts| DateTime::from_timestamp(ts, 0))
+        .unwrap_or_else(Utc::now);
+    let window_start = now - chrono::Duration::days(HEATMAP_DAYS);
+
+    // Bucket commits per calendar date (days since window start).
+    let mut per_day: HashMap<i64, usize> = HashMap::new();
+    for &ts in timestamps {
+        if let Some(dt) = DateTime::from_timestamp(ts, 0) {
+            if dt >= window_start {
+                let day_offset = (dt - window_start).num_days();
+                *per_day.entry(day_offset).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let max_count = per_day.values().copied().max().unwrap_or(0);
+    let total_days = HEATMAP_DAYS as usize;
+    let weeks = (total_days / 7) + 1;
+
+    div()
+        .id("commit-heatmap")
+        .flex()
+        .flex_col()
+        .gap_1()
+        .child(
+            div()
+                .text_xs()
+                .font_weight(FontWeight::BOLD)
+                .text_color(rgb(0x616161))
+                .child("Contribution Heatmap (last 365 days)")
+        )
+        .child(
+            div()
+                .flex()
+                .gap_0p5()
+                .children((0..weeks).map(|week| {
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_0p5()
+                        .children((0..7).map(move |weekday| {
+                            let day_offset = (week * 7 + weekday) as i64;
+                            let count = per_day.get(&day_offset).copied().unwrap_or(0);
+                            let bucket = intensity_bucket(count, max_count);
+
+                            div()
+                                .w(px(10.0))
+                                .h(px(10.0))
+                                .rounded(px(2.0))
+                                .bg(bucket_color(bucket))
+                        }))
+                }))
+        )
+}
+
+/// Hour (0-23) × weekday (Mon-Sun) punch card: which hours/days see the most commits.
+fn render_punch_card(timestamps: &[i64]) -> Div {
+    let mut per_cell: HashMap<(u32, u32), usize> = HashMap::new();
+    for &ts in timestamps {
+        if let Some(dt) = DateTime::from_timestamp(ts, 0) {
+            let key = (dt.weekday().num_days_from_monday(), dt.hour());
+            *per_cell.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    let max_count = per_cell.values().copied().max().unwrap_or(0);
+    let weekday_labels = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+    div()
+        .id("commit-punch-card")
+        .flex()
+        .flex_col()
+        .gap_1()
+        .child(
+            div()
+                .text_xs()
+                .font_weight(FontWeight::BOLD)
+                .text_color(rgb(0x616161))
+                .child("Punch Card (hour × weekday)")
+        )
+        .children((0..7).map(|weekday| {
+            div()
+                .flex()
+                .gap_0p5()
+                .items_center()
+                .child(
+                    div()
+                        .w(px(28.0))
+                        .text_xs()
+                        .text_color(rgb(0x9e9e9e))
+                        .child(weekday_labels[weekday as usize])
+                )
+                .children((0..24).map(move |hour| {
+                    let count = per_cell.get(&(weekday, hour)).copied().unwrap_or(0);
+                    let bucket = intensity_bucket(count, max_count);
+
+                    div()
+                        .w(px(8.0))
+                        .h(px(8.0))
+                        .rounded(px(2.0))
+                        .bg(bucket_color(bucket))
+                }))
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_low_nonzero_count_against_a_much_larger_max_lands_in_the_lightest_bucket() {
+        // Regression test: the bucket math used to `.ceil()` a fractional ratio and then
+        // unconditionally add 1 on top, so bucket 1 (the lightest green) was unreachable -
+        // any nonzero count jumped straight to bucket 2.
+        assert_eq!(intensity_bucket(1, 1000), 1);
+    }
+
+    #[test]
+    fn a_count_matching_the_max_lands_in_the_darkest_bucket() {
+        assert_eq!(intensity_bucket(42, 42), HEATMAP_BUCKETS - 1);
+    }
+
+    #[test]
+    fn a_zero_count_always_lands_in_the_empty_bucket() {
+        assert_eq!(intensity_bucket(0, 42), 0);
+        assert_eq!(intensity_bucket(0, 0), 0);
+    }
+}
@@ -0,0 +1,248 @@
+// Token-aware collapsing for oversized timeline content (tool output, long
+// messages). Measures content in estimated BPE-style tokens rather than bytes
+// so CJK text and dense code don't get mis-measured, and truncates at line
+// boundaries that never split a multibyte character or leave a code fence open.
+
+/// Which part of the content to keep when it exceeds the token budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TruncateDirection {
+    /// Keep the head, drop the tail. Good for long assistant messages.
+    End,
+    /// Keep the tail, drop the head. Good for streaming logs where the
+    /// interesting error is the last thing printed.
+    Start,
+    /// Keep both head and tail, drop the center. Good for diffs, where
+    /// both the file header and the final hunk carry useful context.
+    Middle,
+}
+
+#[derive(Debug, Clone)]
+pub struct TruncateConfig {
+    pub direction: TruncateDirection,
+    pub token_budget: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Truncated {
+    /// The text to show by default (full text if `truncated` is false).
+    pub visible: String,
+    /// Estimated token count of the *full* untruncated content.
+    pub total_tokens: usize,
+    /// Whether `visible` actually had anything cut out of it.
+    pub truncated: bool,
+}
+
+/// Rough approximation of a BPE tokenizer's token count, without pulling in an
+/// actual vocabulary: ASCII word/number runs cost ~1 token per 4 characters
+/// (matching typical English BPE merge rates), while CJK, emoji, and
+/// punctuation each tend to stand alone as their own token.
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut tokens = 0usize;
+    let mut ascii_run = 0usize;
+
+    for ch in text.chars() {
+        if ch.is_ascii() && (ch.is_alphanumeric() || ch == '_') {
+            ascii_run += 1;
+            continue;
+        }
+        if ascii_run > 0 {
+            tokens += ascii_run.div_ceil(4).max(1);
+            ascii_run = 0;
+        }
+        if ch.is_whitespace() {
+            continue;
+        }
+        tokens += 1;
+    }
+    if ascii_run > 0 {
+        tokens += ascii_run.div_ceil(4).max(1);
+    }
+
+    tokens
+}
+
+/// True if `line` toggles whether we're inside a fenced code block (```` ``` ````).
+fn is_fence_marker(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// Index (exclusive) up to which `lines[..idx]` contains only balanced code
+/// fences, walking backward from `idx` until fence parity is even.
+fn nearest_fence_safe_boundary_backward(lines: &[&str], mut idx: usize) -> usize {
+    loop {
+        let open_fences = lines[..idx].iter().filter(|l| is_fence_marker(l)).count();
+        if open_fences % 2 == 0 {
+            return idx;
+        }
+        if idx == 0 {
+            return 0;
+        }
+        idx -= 1;
+    }
+}
+
+/// Index (inclusive start) down to which `lines[idx..]` contains only balanced
+/// code fences, walking forward from `idx` until fence parity is even.
+fn nearest_fence_safe_boundary_forward(lines: &[&str], mut idx: usize) -> usize {
+    loop {
+        let open_fences = lines[idx..].iter().filter(|l| is_fence_marker(l)).count();
+        if open_fences % 2 == 0 {
+            return idx;
+        }
+        if idx + 1 >= lines.len() {
+            return lines.len();
+        }
+        idx += 1;
+    }
+}
+
+/// Truncates `text` to fit within `config.token_budget`, never splitting a
+/// multibyte character (all cuts land on line boundaries, which are always
+/// char boundaries) and never leaving a code fence open.
+pub fn truncate_content(text: &str, config: &TruncateConfig) -> Truncated {
+    let total_tokens = estimate_tokens(text);
+    if total_tokens <= config.token_budget {
+        return Truncated {
+            visible: text.to_string(),
+            total_tokens,
+            truncated: false,
+        };
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+
+    match config.direction {
+        TruncateDirection::End => {
+            let cut = take_from_start(&lines, config.token_budget);
+            let cut = nearest_fence_safe_boundary_backward(&lines, cut);
+            let hidden = lines.len() - cut;
+            let mut visible = lines[..cut].join("\n");
+            if hidden > 0 {
+                visible.push_str(&format!("\n… {} lines hidden …", hidden));
+            }
+            Truncated { visible, total_tokens, truncated: true }
+        }
+        TruncateDirection::Start => {
+            let keep_from = take_from_end(&lines, config.token_budget);
+            let keep_from = nearest_fence_safe_boundary_forward(&lines, keep_from);
+            let hidden = keep_from;
+            let mut visible = String::new();
+            if hidden > 0 {
+                visible.push_str(&format!("… {} lines hidden …\n", hidden));
+            }
+            visible.push_str(&lines[keep_from..].join("\n"));
+            Truncated { visible, total_tokens, truncated: true }
+        }
+        TruncateDirection::Middle => {
+            let half_budget = config.token_budget / 2;
+            let head_cut = nearest_fence_safe_boundary_backward(
+                &lines,
+                take_from_start(&lines, half_budget),
+            );
+            let tail_from = nearest_fence_safe_boundary_forward(
+                &lines,
+                take_from_end(&lines, config.token_budget - half_budget),
+            );
+            let tail_from = tail_from.max(head_cut);
+            let hidden = tail_from - head_cut;
+
+            let mut visible = lines[..head_cut].join("\n");
+            if hidden > 0 {
+                visible.push_str(&format!("\n… {} lines hidden …\n", hidden));
+            }
+            visible.push_str(&lines[tail_from..].join("\n"));
+            Truncated { visible, total_tokens, truncated: true }
+        }
+    }
+}
+
+/// Largest `n` such that `lines[..n]` fits within `budget` tokens.
+fn take_from_start(lines: &[&str], budget: usize) -> usize {
+    let mut used = 0usize;
+    for (i, line) in lines.iter().enumerate() {
+        used += estimate_tokens(line);
+        if used > budget {
+            return i;
+        }
+    }
+    lines.len()
+}
+
+/// Smallest `n` such that `lines[n..]` fits within `budget` tokens.
+fn take_from_end(lines: &[&str], budget: usize) -> usize {
+    let mut used = 0usize;
+    for (i, line) in lines.iter().enumerate().rev() {
+        used += estimate_tokens(line);
+        if used > budget {
+            return i + 1;
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_within_budget_is_not_truncated() {
+        let config = TruncateConfig { direction: TruncateDirection::End, token_budget: 100 };
+        let result = truncate_content("short text", &config);
+        assert!(!result.truncated);
+        assert_eq!(result.visible, "short text");
+    }
+
+    #[test]
+    fn end_direction_keeps_the_head_and_marks_hidden_lines() {
+        let text = (0..50).map(|i| format!("line {i}")).collect::<Vec<_>>().join("\n");
+        let config = TruncateConfig { direction: TruncateDirection::End, token_budget: 10 };
+        let result = truncate_content(&text, &config);
+
+        assert!(result.truncated);
+        assert!(result.visible.starts_with("line 0"));
+        assert!(result.visible.contains("lines hidden"));
+        assert!(!result.visible.contains("line 49"));
+    }
+
+    #[test]
+    fn start_direction_keeps_the_tail_and_marks_hidden_lines() {
+        let text = (0..50).map(|i| format!("line {i}")).collect::<Vec<_>>().join("\n");
+        let config = TruncateConfig { direction: TruncateDirection::Start, token_budget: 10 };
+        let result = truncate_content(&text, &config);
+
+        assert!(result.truncated);
+        assert!(result.visible.ends_with("line 49"));
+        assert!(result.visible.contains("lines hidden"));
+        assert!(!result.visible.contains("line 0"));
+    }
+
+    #[test]
+    fn middle_direction_keeps_head_and_tail_and_drops_the_center() {
+        let text = (0..50).map(|i| format!("line {i}")).collect::<Vec<_>>().join("\n");
+        let config = TruncateConfig { direction: TruncateDirection::Middle, token_budget: 10 };
+        let result = truncate_content(&text, &config);
+
+        assert!(result.truncated);
+        assert!(result.visible.starts_with("line 0"));
+        assert!(result.visible.ends_with("line 49"));
+        assert!(result.visible.contains("lines hidden"));
+    }
+
+    #[test]
+    fn truncation_never_cuts_inside_an_open_code_fence() {
+        let text = vec![
+            "intro line",
+            "```rust",
+            "fn long_function_body_that_pushes_past_the_budget() {}",
+            "more code here to pad it out",
+            "```",
+            "trailing line",
+        ]
+        .join("\n");
+        let config = TruncateConfig { direction: TruncateDirection::End, token_budget: 3 };
+        let result = truncate_content(&text, &config);
+
+        let fence_count = result.visible.matches("```").count();
+        assert_eq!(fence_count % 2, 0, "must not leave a code fence open: {:?}", result.visible);
+    }
+}
@@ -0,0 +1,148 @@
+// Virtualized timeline: renders only the events intersecting the viewport
+// (via GPUI's `uniform_list`, which windows + recycles automatically) instead
+// of materializing a `Div` per event on every frame. Event identity comes from
+// vector position, which is stable here because the timeline is append-only -
+// nothing is ever reordered or removed out from under a row mid-session.
+use gpui::*;
+use gpui::prelude::*;
+
+use crate::types::TimelineEvent;
+use super::components::render_timeline_event;
+use super::theme::active_theme;
+
+/// Owns the timeline's events and scroll state, and renders them through a
+/// `uniform_list` so long sessions (thousands of tool calls / findings) only
+/// pay for the rows currently on screen.
+pub struct VirtualizedTimeline {
+    events: Vec<TimelineEvent>,
+    scroll_handle: UniformListScrollHandle,
+    /// Auto-scrolls to the newest event on `append` until the user scrolls up,
+    /// at which point we stop following until they ask to jump back down.
+    stick_to_bottom: bool,
+}
+
+impl VirtualizedTimeline {
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            scroll_handle: UniformListScrollHandle::new(),
+            stick_to_bottom: true,
+        }
+    }
+
+    pub fn events(&self) -> &[TimelineEvent] {
+        &self.events
+    }
+
+    /// Replaces the full event list (e.g. loading a past session from the
+    /// audit store).
+    pub fn set_events(&mut self, events: Vec<TimelineEvent>, cx: &mut Context<Self>) {
+        self.events = events;
+        self.stick_to_bottom = true;
+        cx.notify();
+    }
+
+    /// Appends a new event, keeping earlier rows untouched. Scrolls to the new
+    /// bottom if the view is currently stuck to it.
+    pub fn append(&mut self, event: TimelineEvent, cx: &mut Context<Self>) {
+        self.events.push(event);
+        if self.stick_to_bottom {
+            self.scroll_handle.scroll_to_item(self.events.len() - 1);
+        }
+        cx.notify();
+    }
+
+    /// Replaces the last event in place - used for in-flight
+    /// `render_streaming_*` rows whose content grows without the row itself
+    /// being re-created (e.g. a thought or tool call buffer filling in).
+    pub fn update_last(&mut self, event: TimelineEvent, cx: &mut Context<Self>) {
+        match self.events.last_mut() {
+            Some(last) => *last = event,
+            None => self.events.push(event),
+        }
+        if self.stick_to_bottom {
+            self.scroll_handle.scroll_to_item(self.events.len() - 1);
+        }
+        cx.notify();
+    }
+
+    pub fn is_stuck_to_bottom(&self) -> bool {
+        self.stick_to_bottom
+    }
+
+    /// Re-engages auto-follow and jumps to the newest event.
+    pub fn jump_to_bottom(&mut self, cx: &mut Context<Self>) {
+        self.stick_to_bottom = true;
+        if !self.events.is_empty() {
+            self.scroll_handle.scroll_to_item(self.events.len() - 1);
+        }
+        cx.notify();
+    }
+}
+
+impl Default for VirtualizedTimeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for VirtualizedTimeline {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = active_theme(cx);
+        let len = self.events.len();
+        let stuck = self.stick_to_bottom;
+
+        div()
+            .relative()
+            .size_full()
+            .child(
+                uniform_list(
+                    cx.entity().clone(),
+                    "virtualized-timeline-list",
+                    len,
+                    move |this, range, _window, cx| {
+                        range
+                            .map(|ix| {
+                                div()
+                                    .id(("timeline-row", ix))
+                                    .w_full()
+                                    .py_2()
+                                    .child(render_timeline_event(&this.events[ix], cx))
+                                    .into_any_element()
+                            })
+                            .collect::<Vec<_>>()
+                    },
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .size_full()
+                // Any manual wheel input means the user is steering - stop
+                // auto-following until they ask to jump back to the bottom.
+                .on_scroll_wheel(cx.listener(|this, _event, _window, cx| {
+                    if this.stick_to_bottom {
+                        this.stick_to_bottom = false;
+                        cx.notify();
+                    }
+                })),
+            )
+            .when(!stuck && len > 0, |container| {
+                container.child(
+                    div()
+                        .absolute()
+                        .bottom_2()
+                        .right_2()
+                        .id("jump-to-bottom")
+                        .px_3()
+                        .py_1()
+                        .bg(theme.link_accent)
+                        .text_color(rgb(0xffffff))
+                        .text_xs()
+                        .rounded_md()
+                        .cursor_pointer()
+                        .child("⬇ Jump to latest")
+                        .on_click(cx.listener(|this, _event, _window, cx| {
+                            this.jump_to_bottom(cx);
+                        })),
+                )
+            })
+    }
+}
@@ -0,0 +1,189 @@
+// Webhook dispatch subsystem - notifies a user-configured endpoint about flagged
+// patterns using the Standard Webhooks signing scheme, the same way a forge server
+// posts signed webhooks to downstream consumers (Slack, Gitea, CI, ...).
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::types::{now_timestamp, to_unix_seconds, LifecycleEvent};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const MAX_ATTEMPTS: u32 = 4;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Where flagged patterns get POSTed, and the shared secret used to sign them.
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub endpoint: String,
+    /// The signing secret in its raw `whsec_`-prefixed form, as issued.
+    pub secret: String,
+}
+
+/// An event worth notifying a downstream consumer about. Produced in
+/// `on_repo_selected`/`on_send_message` when enrichment flags something.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum UserNotification {
+    HighSeverityPattern { repo_path: String, title: String, description: String, severity: f32 },
+    RepoFlagged { repo_path: String, issue: String },
+    EnrichmentComplete { repo_path: String, commit_count: usize, pattern_count: usize },
+}
+
+impl UserNotification {
+    fn kind(&self) -> &'static str {
+        match self {
+            UserNotification::HighSeverityPattern { .. } => "Webhook: high-severity pattern",
+            UserNotification::RepoFlagged { .. } => "Webhook: repo flagged",
+            UserNotification::EnrichmentComplete { .. } => "Webhook: enrichment complete",
+        }
+    }
+}
+
+/// Dispatches `UserNotification`s to a configured webhook endpoint, retrying with
+/// exponential backoff and reporting delivery status back via `status_callback`.
+pub struct WebhookDispatcher {
+    sender: mpsc::UnboundedSender<UserNotification>,
+}
+
+impl WebhookDispatcher {
+    /// Spawns the background sender task. Must be called from within a Tokio runtime.
+    pub fn new<F>(config: WebhookConfig, status_callback: F) -> Self
+    where
+        F: Fn(LifecycleEvent) + Send + Sync + 'static,
+    {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(Self::sender_loop(config, receiver, Arc::new(status_callback)));
+        Self { sender }
+    }
+
+    /// Enqueues a notification for delivery. Returns immediately - the actual
+    /// HTTP POST happens on the background sender task.
+    pub fn notify(&self, notification: UserNotification) -> Result<()> {
+        self.sender.send(notification).map_err(|_| anyhow::anyhow!("webhook dispatcher has shut down"))
+    }
+
+    async fn sender_loop(
+        config: WebhookConfig,
+        mut receiver: mpsc::UnboundedReceiver<UserNotification>,
+        status_callback: Arc<dyn Fn(LifecycleEvent) + Send + Sync>,
+    ) {
+        let client = reqwest::Client::new();
+        let msg_counter = AtomicU64::new(0);
+
+        while let Some(notification) = receiver.recv().await {
+            let kind = notification.kind().to_string();
+            status_callback(LifecycleEvent::running(kind.clone()));
+
+            let body = match serde_json::to_string(&notification) {
+                Ok(body) => body,
+                Err(e) => {
+                    status_callback(LifecycleEvent::failed(kind, e.to_string()));
+                    continue;
+                }
+            };
+
+            let msg_id = format!("msg_{}", msg_counter.fetch_add(1, Ordering::Relaxed));
+            match Self::deliver_with_retry(&client, &config, &msg_id, &body).await {
+                Ok(()) => status_callback(LifecycleEvent::completed(kind)),
+                Err(e) => status_callback(LifecycleEvent::failed(kind, e.to_string())),
+            }
+        }
+    }
+
+    async fn deliver_with_retry(
+        client: &reqwest::Client,
+        config: &WebhookConfig,
+        msg_id: &str,
+        body: &str,
+    ) -> Result<()> {
+        let mut backoff = INITIAL_BACKOFF;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            match Self::deliver_once(client, config, msg_id, body).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt == MAX_ATTEMPTS => return Err(e),
+                Err(_) => {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+
+        unreachable!("the loop above always returns by the final attempt")
+    }
+
+    async fn deliver_once(client: &reqwest::Client, config: &WebhookConfig, msg_id: &str, body: &str) -> Result<()> {
+        let timestamp = to_unix_seconds(now_timestamp());
+        let signature = sign(&config.secret, msg_id, timestamp, body)?;
+
+        let response = client
+            .post(&config.endpoint)
+            .header("webhook-id", msg_id)
+            .header("webhook-timestamp", timestamp.to_string())
+            .header("webhook-signature", format!("v1,{}", signature))
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .context("Failed to send webhook request")?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Webhook endpoint returned {}", response.status()))
+        }
+    }
+}
+
+/// Computes a Standard Webhooks signature: base64(`HMAC-SHA256(secret, "{msg_id}.{timestamp}.{body}")`).
+/// `secret` is the raw signing key, optionally `whsec_`-prefixed.
+fn sign(secret: &str, msg_id: &str, timestamp: i64, body: &str) -> Result<String> {
+    let key = secret.strip_prefix("whsec_").unwrap_or(secret);
+    let key_bytes =
+        base64::engine::general_purpose::STANDARD.decode(key).context("Webhook secret is not valid base64")?;
+
+    let mut mac = HmacSha256::new_from_slice(&key_bytes).context("Invalid webhook signing key")?;
+    mac.update(format!("{}.{}.{}", msg_id, timestamp, body).as_bytes());
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_strips_the_whsec_prefix_before_decoding() {
+        let prefixed = sign("whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw", "msg_1", 1_614_265_330, "{}").unwrap();
+        let bare = sign("MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw", "msg_1", 1_614_265_330, "{}").unwrap();
+        assert_eq!(prefixed, bare);
+    }
+
+    #[test]
+    fn sign_is_deterministic_for_the_same_inputs() {
+        let a = sign("whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw", "msg_1", 1_614_265_330, r#"{"ok":true}"#).unwrap();
+        let b = sign("whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw", "msg_1", 1_614_265_330, r#"{"ok":true}"#).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn sign_changes_when_the_body_changes() {
+        let secret = "whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw";
+        let a = sign(secret, "msg_1", 1_614_265_330, r#"{"ok":true}"#).unwrap();
+        let b = sign(secret, "msg_1", 1_614_265_330, r#"{"ok":false}"#).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn sign_rejects_a_non_base64_secret() {
+        assert!(sign("whsec_not valid base64!!", "msg_1", 1_614_265_330, "{}").is_err());
+    }
+}
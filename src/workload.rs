@@ -0,0 +1,231 @@
+// Session record/replay - captures a live run's event sequence into a versioned JSON
+// workload file, replays it deterministically through the same handling path, and
+// benchmarks that replay to catch performance regressions in timeline handling.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::types::TimelineEvent;
+#[cfg(test)]
+use crate::types::now_timestamp;
+#[cfg(test)]
+use uuid::Uuid;
+
+const WORKLOAD_FORMAT_VERSION: u32 = 1;
+
+/// One recorded event plus how long after the previous event it arrived, in
+/// milliseconds - replay sleeps this long before dispatching, so timing-sensitive
+/// rendering (streaming chunks, debounced UI) reproduces faithfully.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadEvent {
+    pub event: TimelineEvent,
+    pub delay_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workload {
+    pub format_version: u32,
+    pub session_id: String,
+    pub events: Vec<WorkloadEvent>,
+}
+
+/// Captures a live run's `TimelineEvent` sequence, timestamping inter-event gaps as
+/// they're recorded so a later replay can reproduce the same pacing.
+pub struct WorkloadRecorder {
+    session_id: String,
+    events: Vec<WorkloadEvent>,
+    last_event_at: Instant,
+}
+
+impl WorkloadRecorder {
+    pub fn new(session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            events: Vec::new(),
+            last_event_at: Instant::now(),
+        }
+    }
+
+    pub fn record(&mut self, event: TimelineEvent) {
+        let now = Instant::now();
+        let delay_ms = now.duration_since(self.last_event_at).as_millis() as u64;
+        self.last_event_at = now;
+        self.events.push(WorkloadEvent { event, delay_ms });
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let workload = Workload {
+            format_version: WORKLOAD_FORMAT_VERSION,
+            session_id: self.session_id.clone(),
+            events: self.events.clone(),
+        };
+        let json = serde_json::to_string_pretty(&workload).context("Failed to serialize workload")?;
+        std::fs::write(path, json).context("Failed to write workload file")?;
+        Ok(())
+    }
+}
+
+impl Workload {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path).context("Failed to read workload file")?;
+        let workload: Workload =
+            serde_json::from_str(&json).context("Failed to parse workload file")?;
+        if workload.format_version != WORKLOAD_FORMAT_VERSION {
+            return Err(anyhow::anyhow!(
+                "unsupported workload format version {} (expected {})",
+                workload.format_version,
+                WORKLOAD_FORMAT_VERSION
+            ));
+        }
+        Ok(workload)
+    }
+
+    /// Replays each event through `handler` in order, sleeping the recorded inter-event
+    /// delay beforehand so the pacing matches the original run.
+    pub fn replay<F: FnMut(&TimelineEvent)>(&self, mut handler: F) {
+        for workload_event in &self.events {
+            if workload_event.delay_ms > 0 {
+                std::thread::sleep(std::time::Duration::from_millis(workload_event.delay_ms));
+            }
+            handler(&workload_event.event);
+        }
+    }
+}
+
+// ============================================================================
+// Benchmark harness
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub workload_path: String,
+    pub event_count: usize,
+    pub peak_timeline_size: usize,
+    pub total_wall_clock_ms: u64,
+    pub per_event_latency_ms: Vec<f64>,
+    pub mean_latency_ms: f64,
+    pub max_latency_ms: f64,
+}
+
+/// Replays a workload with pacing disabled (events dispatched back-to-back) through
+/// `handler` - the same timeline-handling path a caller would wire up for real use -
+/// and measures per-event processing latency, peak timeline size, and total wall-clock.
+pub fn bench_workload<F: FnMut(&TimelineEvent)>(path: &Path, mut handler: F) -> Result<BenchReport> {
+    let workload = Workload::load(path)?;
+
+    let mut per_event_latency_ms = Vec::with_capacity(workload.events.len());
+    let mut peak_timeline_size = 0;
+
+    let start = Instant::now();
+    for (seen, workload_event) in workload.events.iter().enumerate() {
+        let event_start = Instant::now();
+        handler(&workload_event.event);
+        peak_timeline_size = peak_timeline_size.max(seen + 1);
+        per_event_latency_ms.push(event_start.elapsed().as_secs_f64() * 1000.0);
+    }
+    let total_wall_clock_ms = start.elapsed().as_millis() as u64;
+
+    let mean_latency_ms = if per_event_latency_ms.is_empty() {
+        0.0
+    } else {
+        per_event_latency_ms.iter().sum::<f64>() / per_event_latency_ms.len() as f64
+    };
+    let max_latency_ms = per_event_latency_ms.iter().cloned().fold(0.0, f64::max);
+
+    Ok(BenchReport {
+        workload_path: path.display().to_string(),
+        event_count: workload.events.len(),
+        peak_timeline_size,
+        total_wall_clock_ms,
+        per_event_latency_ms,
+        mean_latency_ms,
+        max_latency_ms,
+    })
+}
+
+/// Runs `bench_workload` over every path, optionally POSTing each report to a results
+/// endpoint (e.g. a dashboard tracking regression data across CI runs). `make_handler`
+/// is called fresh for each path so per-workload state (e.g. a handler's own timeline)
+/// doesn't leak between benchmarks.
+pub fn bench_many(
+    paths: &[std::path::PathBuf],
+    report_endpoint: Option<&str>,
+    mut make_handler: impl FnMut() -> Box<dyn FnMut(&TimelineEvent)>,
+) -> Result<Vec<BenchReport>> {
+    let mut reports = Vec::with_capacity(paths.len());
+    for path in paths {
+        let report = bench_workload(path, make_handler())?;
+        if let Some(endpoint) = report_endpoint {
+            if let Err(err) = post_report(endpoint, &report) {
+                eprintln!("Failed to POST bench report for {:?}: {}", path, err);
+            }
+        }
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// Minimal dependency-free `POST` over a raw `TcpStream` - this harness doesn't
+/// warrant pulling in a full HTTP client just to ship a JSON blob to a results
+/// endpoint. Plain HTTP only; `endpoint` is `host:port/path`, no TLS.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn workload_with_events(count: usize) -> Workload {
+        Workload {
+            format_version: WORKLOAD_FORMAT_VERSION,
+            session_id: "bench-test".to_string(),
+            events: (0..count)
+                .map(|i| WorkloadEvent {
+                    event: TimelineEvent::UserMessage { content: i.to_string(), timestamp: now_timestamp() },
+                    delay_ms: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn bench_workload_runs_every_event_through_the_handler() {
+        let tmp = std::env::temp_dir().join(format!("codex-d-bench-test-{}.json", Uuid::new_v4()));
+        let workload = workload_with_events(5);
+        std::fs::write(&tmp, serde_json::to_string(&workload).unwrap()).unwrap();
+
+        let seen = Arc::new(AtomicUsize::new(0));
+        let seen_in_handler = Arc::clone(&seen);
+        let report = bench_workload(&tmp, move |_event| {
+            seen_in_handler.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        std::fs::remove_file(&tmp).ok();
+
+        assert_eq!(seen.load(Ordering::SeqCst), 5);
+        assert_eq!(report.event_count, 5);
+        assert_eq!(report.peak_timeline_size, 5);
+        assert_eq!(report.per_event_latency_ms.len(), 5);
+    }
+}
+
+fn post_report(endpoint: &str, report: &BenchReport) -> Result<()> {
+    let (authority, path) = endpoint.split_once('/').unwrap_or((endpoint, ""));
+    let body = serde_json::to_vec(report).context("Failed to serialize bench report")?;
+
+    let mut stream =
+        TcpStream::connect(authority).with_context(|| format!("Failed to connect to {}", authority))?;
+    let request = format!(
+        "POST /{} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        path,
+        authority,
+        body.len(),
+    );
+    stream.write_all(request.as_bytes())?;
+    stream.write_all(&body)?;
+    Ok(())
+}